@@ -0,0 +1,49 @@
+// Helpers shared by the benchmarks.
+
+#![allow(dead_code)]
+
+use bevior_tree::prelude::*;
+use bevy::prelude::*;
+
+/// Task that keeps running, so that every tree is resumed on every tick.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+pub struct RunningTaskDefinition;
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl TaskDefinition for RunningTaskDefinition {
+    fn build_checker(&self) -> Box<TaskChecker> {
+        Box::new(IntoSystem::into_system(|In(_): In<Entity>| {
+            TaskStatus::Running
+        }))
+    }
+    fn build_event_listeners(&self) -> Vec<(TaskEvent, Box<TaskEventListener>)> {
+        vec![]
+    }
+}
+
+pub fn running_task() -> Box<dyn Node> {
+    Box::new(TaskBridge::new(Box::new(RunningTaskDefinition)))
+}
+
+pub fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        BehaviorTreePlugin::default(),
+    ));
+    app
+}
+
+/// Spawns the trees with the node, then updates once to begin them.
+/// The benchmarks measure the ticks after that.
+pub fn spawn_trees(app: &mut App, node: impl Node, count: usize, bundle: impl Bundle + Clone) {
+    let tree = BehaviorTree::from_node(
+        node,
+        &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+    );
+    for _ in 0..count {
+        app.world_mut().spawn((tree.clone(), bundle.clone()));
+    }
+    app.update();
+}
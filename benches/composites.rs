@@ -0,0 +1,135 @@
+// Measures the per-tick cost of the composite nodes and the task bridge.
+// Run with `cargo bench --bench composites`.
+// Each iteration is one tick of the app with `TREES` trees.
+
+mod common;
+
+use bevior_tree::prelude::*;
+use bevy::prelude::*;
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+const TREES: usize = 100;
+
+/// Task that completes on begin, with the event listeners.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+struct InstantTaskDefinition {
+    listeners: usize,
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl TaskDefinition for InstantTaskDefinition {
+    fn build_checker(&self) -> Box<TaskChecker> {
+        Box::new(IntoSystem::into_system(|In(_): In<Entity>| {
+            TaskStatus::Complete(NodeResult::Success)
+        }))
+    }
+    fn build_event_listeners(&self) -> Vec<(TaskEvent, Box<TaskEventListener>)> {
+        (0..self.listeners)
+            .flat_map(|_| insert_while_running(Marker))
+            .collect()
+    }
+}
+
+#[derive(Component, Clone)]
+struct Marker;
+
+fn instant_task(listeners: usize) -> Box<dyn Node> {
+    Box::new(TaskBridge::new(Box::new(InstantTaskDefinition {
+        listeners,
+    })))
+}
+
+fn deep_sequence(depth: usize) -> Sequence {
+    (0..depth).fold(Sequence::new(vec![common::running_task()]), |node, _| {
+        Sequence::new(vec![Box::new(node)])
+    })
+}
+
+/// Compares the nested tree with [`CompiledTree`] of the same tree.
+fn bench_deep_sequence(c: &mut Criterion) {
+    let mut group = c.benchmark_group("deep_sequence");
+    for depth in [10, 100] {
+        group.bench_with_input(BenchmarkId::new("nested", depth), &depth, |b, &depth| {
+            let mut app = common::app();
+            common::spawn_trees(&mut app, deep_sequence(depth), TREES, ());
+            b.iter(|| app.update());
+        });
+        group.bench_with_input(BenchmarkId::new("compiled", depth), &depth, |b, &depth| {
+            let mut app = common::app();
+            common::spawn_trees(&mut app, CompiledTree::new(deep_sequence(depth)), TREES, ());
+            b.iter(|| app.update());
+        });
+    }
+    group.finish();
+}
+
+fn bench_wide_parallel(c: &mut Criterion) {
+    let mut group = c.benchmark_group("wide_parallel");
+    for width in [10, 100] {
+        group.bench_with_input(BenchmarkId::from_parameter(width), &width, |b, &width| {
+            let node = Join::new((0..width).map(|_| common::running_task()).collect());
+            let mut app = common::app();
+            common::spawn_trees(&mut app, node, TREES, ());
+            b.iter(|| app.update());
+        });
+    }
+    group.finish();
+}
+
+/// Restarts on every tick, so that the scorers run on every tick.
+fn bench_scored_sequence(c: &mut Criterion) {
+    let mut group = c.benchmark_group("scored_sequence");
+    for scorers in [10, 100] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(scorers),
+            &scorers,
+            |b, &scorers| {
+                let node = ScoreOrderedSequentialAnd::new(
+                    (0..scorers)
+                        .map(|index| {
+                            let scorer: Box<dyn ScorerBuilder> = Box::new(CurveScorerBuilder::new(
+                                ScoreInput::blackboard("score"),
+                                ResponseCurve::Linear {
+                                    slope: index as f32,
+                                    intercept: 0.0,
+                                },
+                            ));
+                            (instant_task(0), scorer)
+                        })
+                        .collect(),
+                );
+                let mut app = common::app();
+                common::spawn_trees(&mut app, node, TREES, RestartPolicy::Always);
+                b.iter(|| app.update());
+            },
+        );
+    }
+    group.finish();
+}
+
+/// Restarts on every tick, so that the enter and exit listeners run and flush their commands on every tick.
+fn bench_task_listeners(c: &mut Criterion) {
+    let mut group = c.benchmark_group("task_listeners");
+    for listeners in [1, 10] {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(listeners),
+            &listeners,
+            |b, &listeners| {
+                let node = Sequence::new(vec![instant_task(listeners)]);
+                let mut app = common::app();
+                common::spawn_trees(&mut app, node, TREES, RestartPolicy::Always);
+                b.iter(|| app.update());
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_deep_sequence,
+    bench_wide_parallel,
+    bench_scored_sequence,
+    bench_task_listeners
+);
+criterion_main!(benches);
@@ -0,0 +1,32 @@
+// Measures the per-tick cost of the tree update system with many running trees.
+// Run with `cargo bench --bench update`.
+
+mod common;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+use bevior_tree::prelude::*;
+
+fn bench_update(c: &mut Criterion) {
+    let mut group = c.benchmark_group("update");
+    for count in [1_000, 10_000] {
+        group.bench_with_input(
+            BenchmarkId::new("running_trees", count),
+            &count,
+            |b, &count| {
+                let mut app = common::app();
+                common::spawn_trees(
+                    &mut app,
+                    Sequence::new(vec![common::running_task()]),
+                    count,
+                    (),
+                );
+                b.iter(|| app.update());
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_update);
+criterion_main!(benches);
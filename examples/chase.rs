@@ -20,7 +20,7 @@ fn init(
     asset_server: Res<AssetServer>,
     mut tree_assets: ResMut<Assets<BehaviorTreeRoot>>,
 ) {
-    commands.spawn(Camera2d::default());
+    commands.spawn(Camera2d);
 
     // Simple player entity
     let player = commands
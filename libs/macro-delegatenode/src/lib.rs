@@ -26,9 +26,15 @@ pub fn delegate_node(attr: TokenStream, item: TokenStream) -> TokenStream {
             fn resume(&self, world: &mut bevy::ecs::world::World, entity: bevy::ecs::entity::Entity, state: Box<dyn bevior_tree::node::NodeState>) -> bevior_tree::node::NodeStatus {
                 self.#delegate.resume(world, entity, state)
             }
+            fn resume_in_place(&self, world: &mut bevy::ecs::world::World, entity: bevy::ecs::entity::Entity, state: &mut Box<dyn bevior_tree::node::NodeState>) -> Option<bevior_tree::node::NodeResult> {
+                self.#delegate.resume_in_place(world, entity, state)
+            }
             fn force_exit(&self, world: &mut bevy::ecs::world::World, entity: bevy::ecs::entity::Entity, state: Box<dyn bevior_tree::node::NodeState>) {
                 self.#delegate.force_exit(world, entity, state)
             }
+            fn flatten(self: Box<Self>) -> bevior_tree::compiled::FlatNode {
+                bevior_tree::node::IntoBoxedNode::into_boxed(Box::new(self.#delegate)).flatten()
+            }
         }
     };
     TokenStream::from(expand)
@@ -0,0 +1,462 @@
+//! Per-entity memory shared by the nodes and the task systems of the tree.
+
+use std::{cmp::Ordering, fmt::Debug, marker::PhantomData};
+
+use bevy::{
+    ecs::{
+        component::Component,
+        entity::Entity,
+        system::{In, IntoSystem, Query, SystemParam},
+        world::World,
+    },
+    platform::collections::HashMap,
+    reflect::{PartialReflect, Reflect},
+};
+
+use crate::{
+    conditional::{CondChecker, CondCheckerBuilder},
+    node::prelude::*,
+};
+
+pub mod prelude {
+    pub use super::{
+        Blackboard, BlackboardCompare, BlackboardHas, BlackboardKey, BlackboardValue, Blackboards,
+        ClearBlackboard, CompareOp, CopyBlackboard, SetBlackboard,
+    };
+}
+
+/// Name of the entry with the type of its value.
+pub struct BlackboardKey<T> {
+    name: String,
+    _marker: PhantomData<fn() -> T>,
+}
+impl<T> BlackboardKey<T> {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            _marker: PhantomData,
+        }
+    }
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+impl<T> Clone for BlackboardKey<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.name.clone())
+    }
+}
+impl<T> Debug for BlackboardKey<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("BlackboardKey").field(&self.name).finish()
+    }
+}
+
+/// Memory of the tree, entries of reflected values by name.
+/// Required by [`crate::BehaviorTree`], so every tree has its own.
+#[derive(Component, Debug, Default)]
+pub struct Blackboard {
+    entries: HashMap<String, Box<dyn PartialReflect>>,
+}
+impl Blackboard {
+    /// Value of the entry, None if missing or of the other type.
+    pub fn get<T: Reflect>(&self, key: &BlackboardKey<T>) -> Option<&T> {
+        self.get_reflect(key.name())?.try_downcast_ref::<T>()
+    }
+    pub fn get_mut<T: Reflect>(&mut self, key: &BlackboardKey<T>) -> Option<&mut T> {
+        self.entries.get_mut(key.name())?.try_downcast_mut::<T>()
+    }
+    pub fn set<T: Reflect>(&mut self, key: &BlackboardKey<T>, value: T) {
+        self.set_reflect(key.name(), Box::new(value));
+    }
+    pub fn remove<T: Reflect>(&mut self, key: &BlackboardKey<T>) -> Option<T> {
+        let value = self.entries.remove(key.name())?;
+        value.try_take::<T>().ok()
+    }
+    pub fn get_reflect(&self, name: &str) -> Option<&dyn PartialReflect> {
+        self.entries.get(name).map(|value| value.as_ref())
+    }
+    pub fn set_reflect(&mut self, name: impl Into<String>, value: Box<dyn PartialReflect>) {
+        self.entries.insert(name.into(), value);
+    }
+    pub fn remove_reflect(&mut self, name: &str) -> Option<Box<dyn PartialReflect>> {
+        self.entries.remove(name)
+    }
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+    /// Copies the entry to another name, returns false if the source is missing.
+    /// The copy is dynamic representation if the type does not support cloning by reflection.
+    pub fn copy(&mut self, from: &str, to: impl Into<String>) -> bool {
+        let Some(value) = self.get_reflect(from) else {
+            return false;
+        };
+        let value = match value.reflect_clone() {
+            Ok(value) => value.into_partial_reflect(),
+            Err(_) => value.to_dynamic(),
+        };
+        self.set_reflect(to, value);
+        true
+    }
+}
+
+/// [`SystemParam`] to access the blackboards of the trees,
+///  e.g. from [`crate::task::TaskChecker`] and [`crate::task::TaskEventListener`] with the entity as input.
+#[derive(SystemParam)]
+pub struct Blackboards<'w, 's> {
+    query: Query<'w, 's, &'static mut Blackboard>,
+}
+impl Blackboards<'_, '_> {
+    pub fn get<T: Reflect>(&self, entity: Entity, key: &BlackboardKey<T>) -> Option<&T> {
+        self.query.get(entity).ok()?.get(key)
+    }
+    /// Sets the value, returns false if the entity has no blackboard.
+    pub fn set<T: Reflect>(&mut self, entity: Entity, key: &BlackboardKey<T>, value: T) -> bool {
+        let Ok(mut blackboard) = self.query.get_mut(entity) else {
+            return false;
+        };
+        blackboard.set(key, value);
+        true
+    }
+    pub fn remove<T: Reflect>(&mut self, entity: Entity, key: &BlackboardKey<T>) -> Option<T> {
+        self.query.get_mut(entity).ok()?.remove(key)
+    }
+    pub fn blackboard(&self, entity: Entity) -> Option<&Blackboard> {
+        self.query.get(entity).ok()
+    }
+    pub fn blackboard_mut(&mut self, entity: Entity) -> Option<bevy::prelude::Mut<'_, Blackboard>> {
+        self.query.get_mut(entity).ok()
+    }
+}
+
+/// Serializable value to write into the blackboard.
+/// Stored as `bool`, `i64`, `f64` or `String`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum BlackboardValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+impl BlackboardValue {
+    fn to_reflect(&self) -> Box<dyn PartialReflect> {
+        match self {
+            Self::Bool(value) => Box::new(*value),
+            Self::Int(value) => Box::new(*value),
+            Self::Float(value) => Box::new(*value),
+            Self::String(value) => Box::new(value.clone()),
+        }
+    }
+    /// Reads the value of primitive types, integers as Int and floats as Float.
+    /// None for integers out of the range of i64.
+    pub fn from_reflect(value: &dyn PartialReflect) -> Option<Self> {
+        macro_rules! try_as {
+            ($variant:ident, $cast:ty, $($ty:ty),*) => {
+                $(if let Some(value) = value.try_downcast_ref::<$ty>() {
+                    return <$cast>::try_from(*value).ok().map(Self::$variant);
+                })*
+            };
+        }
+        try_as!(Int, i64, i64, i32, i16, i8, u32, u16, u8, isize, usize, u64);
+        try_as!(Float, f64, f64, f32);
+        if let Some(value) = value.try_downcast_ref::<bool>() {
+            return Some(Self::Bool(*value));
+        }
+        if let Some(value) = value.try_downcast_ref::<String>() {
+            return Some(Self::String(value.clone()));
+        }
+        None
+    }
+}
+impl PartialOrd for BlackboardValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Self::Bool(a), Self::Bool(b)) => a.partial_cmp(b),
+            (Self::Int(a), Self::Int(b)) => a.partial_cmp(b),
+            (Self::Float(a), Self::Float(b)) => a.partial_cmp(b),
+            (Self::Int(a), Self::Float(b)) => (*a as f64).partial_cmp(b),
+            (Self::Float(a), Self::Int(b)) => a.partial_cmp(&(*b as f64)),
+            (Self::String(a), Self::String(b)) => a.partial_cmp(b),
+            _ => None,
+        }
+    }
+}
+
+fn with_blackboard<R>(
+    world: &mut World,
+    entity: Entity,
+    f: impl FnOnce(&mut Blackboard) -> R,
+) -> R {
+    let mut entity = world.entity_mut(entity);
+    let mut blackboard = entity.entry::<Blackboard>().or_default().into_mut();
+    f(&mut blackboard)
+}
+
+/// State for the nodes in this module, they complete on begin.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(NodeState, Debug)]
+struct BlackboardState;
+
+/// Node that sets the value to the entry, then returns Success.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+#[with_state(BlackboardState)]
+pub struct SetBlackboard {
+    key: String,
+    value: BlackboardValue,
+}
+impl SetBlackboard {
+    pub fn new(key: impl Into<String>, value: BlackboardValue) -> Self {
+        Self {
+            key: key.into(),
+            value,
+        }
+    }
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl Node for SetBlackboard {
+    fn begin(&self, world: &mut World, entity: Entity) -> NodeStatus {
+        self.resume(world, entity, Box::new(BlackboardState))
+    }
+    fn resume(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) -> NodeStatus {
+        let _state = Self::downcast(state).expect("Invalid state type.");
+        with_blackboard(world, entity, |blackboard| {
+            blackboard.set_reflect(self.key.clone(), self.value.to_reflect());
+        });
+        NodeStatus::Complete(NodeResult::Success)
+    }
+    fn force_exit(&self, _world: &mut World, _entity: Entity, _state: Box<dyn NodeState>) {
+        // never
+    }
+}
+
+/// Node that removes the entry, then returns Success.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+#[with_state(BlackboardState)]
+pub struct ClearBlackboard {
+    key: String,
+}
+impl ClearBlackboard {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self { key: key.into() }
+    }
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl Node for ClearBlackboard {
+    fn begin(&self, world: &mut World, entity: Entity) -> NodeStatus {
+        self.resume(world, entity, Box::new(BlackboardState))
+    }
+    fn resume(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) -> NodeStatus {
+        let _state = Self::downcast(state).expect("Invalid state type.");
+        with_blackboard(world, entity, |blackboard| {
+            blackboard.remove_reflect(&self.key);
+        });
+        NodeStatus::Complete(NodeResult::Success)
+    }
+    fn force_exit(&self, _world: &mut World, _entity: Entity, _state: Box<dyn NodeState>) {
+        // never
+    }
+}
+
+/// Node that copies the entry to another name.
+/// Returns Failure if the source entry is missing.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+#[with_state(BlackboardState)]
+pub struct CopyBlackboard {
+    from: String,
+    to: String,
+}
+impl CopyBlackboard {
+    pub fn new(from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self {
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl Node for CopyBlackboard {
+    fn begin(&self, world: &mut World, entity: Entity) -> NodeStatus {
+        self.resume(world, entity, Box::new(BlackboardState))
+    }
+    fn resume(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) -> NodeStatus {
+        let _state = Self::downcast(state).expect("Invalid state type.");
+        let copied = with_blackboard(world, entity, |blackboard| {
+            blackboard.copy(&self.from, self.to.clone())
+        });
+        NodeStatus::Complete(if copied {
+            NodeResult::Success
+        } else {
+            NodeResult::Failure
+        })
+    }
+    fn force_exit(&self, _world: &mut World, _entity: Entity, _state: Box<dyn NodeState>) {
+        // never
+    }
+}
+
+/// Checks if the entry exists.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+pub struct BlackboardHas {
+    pub key: String,
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl CondCheckerBuilder for BlackboardHas {
+    fn build(&self) -> Box<CondChecker> {
+        let key = self.key.clone();
+        Box::new(IntoSystem::into_system(
+            move |In(entity): In<Entity>, blackboards: Blackboards| {
+                blackboards
+                    .blackboard(entity)
+                    .is_some_and(|blackboard| blackboard.contains(&key))
+            },
+        ))
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+impl CompareOp {
+    fn matches(&self, ordering: Option<Ordering>) -> bool {
+        match self {
+            Self::Eq => ordering == Some(Ordering::Equal),
+            Self::Ne => ordering != Some(Ordering::Equal),
+            Self::Lt => ordering == Some(Ordering::Less),
+            Self::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+            Self::Gt => ordering == Some(Ordering::Greater),
+            Self::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+        }
+    }
+}
+
+/// Compares the entry with the value, as `entry op value`.
+/// False if the entry is missing or not comparable, except for [`CompareOp::Ne`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+pub struct BlackboardCompare {
+    pub key: String,
+    pub op: CompareOp,
+    pub value: BlackboardValue,
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl CondCheckerBuilder for BlackboardCompare {
+    fn build(&self) -> Box<CondChecker> {
+        let key = self.key.clone();
+        let op = self.op;
+        let value = self.value.clone();
+        Box::new(IntoSystem::into_system(
+            move |In(entity): In<Entity>, blackboards: Blackboards| {
+                let entry = blackboards
+                    .blackboard(entity)
+                    .and_then(|blackboard| blackboard.get_reflect(&key))
+                    .and_then(BlackboardValue::from_reflect);
+                op.matches(entry.and_then(|entry| entry.partial_cmp(&value)))
+            },
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tester_util::prelude::*;
+
+    #[test]
+    fn test_from_reflect_overflow() {
+        let found = BlackboardValue::from_reflect(&u64::MAX);
+        assert!(
+            found.is_none(),
+            "Integer out of range should not be read. found: {:?}",
+            found
+        );
+        let found = BlackboardValue::from_reflect(&(i64::MAX as u64));
+        assert!(
+            found == Some(BlackboardValue::Int(i64::MAX)),
+            "Integer in range should be read. found: {:?}",
+            found
+        );
+    }
+
+    #[test]
+    fn test_set_copy_clear() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let sequence = SequentialAnd::new(vec![
+            Box::new(SetBlackboard::new("count", BlackboardValue::Int(3))),
+            Box::new(CopyBlackboard::new("count", "copied")),
+            Box::new(ClearBlackboard::new("count")),
+            Box::new(CheckIf::new(BlackboardCompare {
+                key: "copied".to_string(),
+                op: CompareOp::Ge,
+                value: BlackboardValue::Float(2.5),
+            })),
+        ]);
+        let tree = BehaviorTree::from_node(
+            sequence,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        app.update();
+        let blackboard = app.world().get::<Blackboard>(entity).unwrap();
+        assert!(
+            blackboard.get(&BlackboardKey::<i64>::new("copied")) == Some(&3)
+                && !blackboard.contains("count"),
+            "Entries should be set, copied and cleared. found: {:?}",
+            blackboard
+        );
+        let status = app.world().get::<TreeStatus>(entity).unwrap();
+        assert!(
+            status.result() == Some(NodeResult::Success),
+            "Compared entry should match. found: {:?}",
+            status
+        );
+    }
+
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[derive(Debug)]
+    struct PickTargetTask;
+    #[cfg_attr(feature = "serde", typetag::serde)]
+    impl TaskDefinition for PickTargetTask {
+        fn build_checker(&self) -> Box<TaskChecker> {
+            Box::new(IntoSystem::into_system(
+                |In(entity): In<Entity>, mut blackboards: Blackboards| {
+                    blackboards.set(entity, &BlackboardKey::new("target"), entity);
+                    TaskStatus::Complete(NodeResult::Success)
+                },
+            ))
+        }
+        fn build_event_listeners(&self) -> Vec<(TaskEvent, Box<TaskEventListener>)> {
+            vec![]
+        }
+    }
+
+    #[test]
+    fn test_task_writes_blackboard() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let tree = BehaviorTree::from_node(
+            TaskBridge::new(Box::new(PickTargetTask)),
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        app.update();
+        let blackboard = app.world().get::<Blackboard>(entity).unwrap();
+        assert!(
+            blackboard.get(&BlackboardKey::<Entity>::new("target")) == Some(&entity),
+            "Task should write the blackboard. found: {:?}",
+            blackboard
+        );
+    }
+}
@@ -0,0 +1,866 @@
+//! Flat representation of the tree, compiled from the nested nodes.
+
+use std::{num::NonZeroUsize, sync::Mutex};
+
+use bevy::ecs::{entity::Entity, world::World};
+
+use crate::{
+    conditional::{
+        Backoff, LoopCondChecker, LoopCondCheckerBuilder, LoopState, LoopStep, run_loop_checker,
+    },
+    converter::ConverterStrategy,
+    node::prelude::*,
+    sequential::{PickerBuilder, PickerRuntime, ResultStrategy, ScorerBuilder},
+    timer::Stopwatch,
+};
+
+pub mod prelude {
+    pub use super::{CompiledTree, FlatNode};
+}
+
+/// Parts of the node, split by [`Node::flatten`].
+/// Each composite variant runs the same as the node of the same kind.
+pub enum FlatNode {
+    /// Runs with `begin` and `resume` of the node as is.
+    Leaf(Box<dyn Node>),
+    /// Runs the same as [`crate::sequential::ScoredSequence`].
+    Sequential {
+        children: Vec<(Box<dyn Node>, Box<dyn ScorerBuilder>)>,
+        picker: Box<dyn PickerBuilder>,
+        result_strategy: Box<dyn ResultStrategy>,
+    },
+    /// Runs the same as [`crate::parallel::Parallel`].
+    Parallel {
+        children: Vec<Box<dyn Node>>,
+        result_strategy: Box<dyn ResultStrategy>,
+        limit: Option<NonZeroUsize>,
+    },
+    /// Runs the same as [`crate::converter::ResultConverter`].
+    Converter {
+        child: Box<dyn Node>,
+        converter: Box<dyn ConverterStrategy>,
+    },
+    /// Runs the same as [`crate::conditional::ConditionalLoop`].
+    Loop {
+        child: Box<dyn Node>,
+        checker_builder: Box<dyn LoopCondCheckerBuilder>,
+        backoff: Option<Backoff>,
+    },
+}
+
+/// Index of the node in [`CompiledTree`].
+type NodeId = usize;
+
+const ROOT: NodeId = 0;
+
+/// Node in the arena of [`CompiledTree`], referring to the children by index.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+enum CompiledNode {
+    Leaf(Box<dyn Node>),
+    Sequential {
+        children: Vec<NodeId>,
+        scorers: Vec<Box<dyn ScorerBuilder>>,
+        picker: Box<dyn PickerBuilder>,
+        result_strategy: Box<dyn ResultStrategy>,
+        #[cfg_attr(feature = "serde", serde(skip))]
+        runtime: PickerRuntime,
+    },
+    Parallel {
+        children: Vec<NodeId>,
+        result_strategy: Box<dyn ResultStrategy>,
+        limit: Option<NonZeroUsize>,
+    },
+    Converter {
+        child: NodeId,
+        converter: Box<dyn ConverterStrategy>,
+    },
+    Loop {
+        child: NodeId,
+        checker_builder: Box<dyn LoopCondCheckerBuilder>,
+        backoff: Option<Backoff>,
+        #[cfg_attr(feature = "serde", serde(skip))]
+        checker_runtime: Mutex<Option<Box<LoopCondChecker>>>,
+    },
+}
+
+/// Tree compiled into a flat arena of nodes, run with an explicit stack instead of recursive calls.
+/// Sequential, parallel, converter and loop nodes are flattened, with the same results and order of execution.
+/// Other nodes run as leaves, and the nodes under them are not compiled.
+/// These include [`crate::conditional::Guard`], [`crate::conditional::ElseFreeze`], [`crate::subtree::SubTree`],
+/// the reactive sequences, [`crate::sequential::dynamic::DynamicScoredSelector`] and [`crate::parallel::background::MainWithBackground`].
+///
+/// Use it as the root node, e.g. `BehaviorTree::from_node(CompiledTree::new(node), &mut assets)`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+#[with_state(CompiledTreeState)]
+pub struct CompiledTree {
+    nodes: Vec<CompiledNode>,
+}
+impl CompiledTree {
+    pub fn new(node: impl Node) -> Self {
+        Self::from_boxed(Box::new(node))
+    }
+    pub fn from_boxed(node: Box<dyn Node>) -> Self {
+        let mut nodes: Vec<Option<CompiledNode>> = vec![None];
+        let mut queue = vec![(ROOT, node)];
+        while let Some((id, node)) = queue.pop() {
+            let mut reserve = |node: Box<dyn Node>| {
+                let child = nodes.len();
+                nodes.push(None);
+                queue.push((child, node));
+                child
+            };
+            let compiled = match node.flatten() {
+                FlatNode::Leaf(node) => CompiledNode::Leaf(node),
+                FlatNode::Sequential {
+                    children,
+                    picker,
+                    result_strategy,
+                } => {
+                    let (children, scorers) = children
+                        .into_iter()
+                        .map(|(child, scorer)| (reserve(child), scorer))
+                        .unzip();
+                    CompiledNode::Sequential {
+                        children,
+                        scorers,
+                        picker,
+                        result_strategy,
+                        runtime: PickerRuntime::default(),
+                    }
+                }
+                FlatNode::Parallel {
+                    children,
+                    result_strategy,
+                    limit,
+                } => CompiledNode::Parallel {
+                    children: children.into_iter().map(&mut reserve).collect(),
+                    result_strategy,
+                    limit,
+                },
+                FlatNode::Converter { child, converter } => CompiledNode::Converter {
+                    child: reserve(child),
+                    converter,
+                },
+                FlatNode::Loop {
+                    child,
+                    checker_builder,
+                    backoff,
+                } => CompiledNode::Loop {
+                    child: reserve(child),
+                    checker_builder,
+                    backoff,
+                    checker_runtime: Mutex::new(None),
+                },
+            };
+            nodes[id] = Some(compiled);
+        }
+        Self {
+            nodes: nodes
+                .into_iter()
+                .map(|node| node.expect("Node not compiled."))
+                .collect(),
+        }
+    }
+
+    /// Runs the tree until the running nodes are pending or the root completes.
+    /// Resumes from the end of the path, as the nodes above it only pass the pending result through.
+    fn run(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        state: &mut CompiledTreeState,
+        visit: Visit,
+    ) -> Option<NodeResult> {
+        let CompiledTreeState {
+            states,
+            stack,
+            path,
+        } = state;
+        stack.clear();
+        let mut current = match visit {
+            Visit::Resume if !path.is_empty() => {
+                stack.extend_from_slice(&path[..path.len() - 1]);
+                path[path.len() - 1]
+            }
+            _ => ROOT,
+        };
+        // Nodes on the stack still waiting for the same child as on the path.
+        let mut base = stack.len();
+        let stale = visit == Visit::Begin || path.is_empty();
+        let mut action = self.enter(world, entity, states, current, visit);
+        loop {
+            match action {
+                Action::Call(child, visit) => {
+                    stack.push(current);
+                    current = child;
+                    action = self.enter(world, entity, states, current, visit);
+                }
+                Action::Return(None) if stack.len() <= base => {
+                    if stale || base < path.len() - 1 {
+                        self.update_path(states, path, base);
+                    }
+                    return None;
+                }
+                Action::Return(result) => match stack.pop() {
+                    None => return result,
+                    Some(parent) => {
+                        base = base.min(stack.len());
+                        current = parent;
+                        action = self.proceed(world, entity, states, current, result);
+                    }
+                },
+            }
+        }
+    }
+
+    /// Follows the running children of sequential, converter and loop nodes, keeping the first `valid` nodes of the path.
+    fn update_path(&self, states: &[FlatState], path: &mut Vec<NodeId>, valid: usize) {
+        path.truncate(valid);
+        let mut id = path.pop().unwrap_or(ROOT);
+        loop {
+            path.push(id);
+            id = match (&self.nodes[id], &states[id]) {
+                (
+                    CompiledNode::Sequential { children, .. },
+                    FlatState::Sequential { count, indices, .. },
+                ) => children[indices[*count]],
+                (CompiledNode::Converter { child, .. }, FlatState::Converter) => *child,
+                (CompiledNode::Loop { child, .. }, FlatState::Loop { running: true, .. }) => *child,
+                _ => return,
+            };
+        }
+    }
+
+    /// Begins or resumes the node.
+    fn enter(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        states: &mut [FlatState],
+        id: NodeId,
+        visit: Visit,
+    ) -> Action {
+        match (&self.nodes[id], visit) {
+            (CompiledNode::Leaf(node), Visit::Begin) => match node.begin(world, entity) {
+                NodeStatus::Pending(state) => {
+                    states[id] = FlatState::Leaf(state);
+                    Action::Return(None)
+                }
+                NodeStatus::Complete(result) => Action::Return(Some(result)),
+                NodeStatus::Beginning => panic!("Unexpected NodeStatus::Beginning."),
+            },
+            (CompiledNode::Leaf(node), Visit::Resume) => {
+                let FlatState::Leaf(state) = &mut states[id] else {
+                    panic!("Invalid state.");
+                };
+                let result = node.resume_in_place(world, entity, state);
+                if result.is_some() {
+                    states[id] = FlatState::Idle;
+                }
+                Action::Return(result)
+            }
+            (
+                CompiledNode::Sequential {
+                    scorers,
+                    picker,
+                    runtime,
+                    ..
+                },
+                Visit::Begin,
+            ) => {
+                let indices = runtime.pick(
+                    world,
+                    entity,
+                    scorers.iter().map(|scorer| scorer.as_ref()),
+                    picker.as_ref(),
+                );
+                states[id] = FlatState::Sequential {
+                    count: 0,
+                    results: indices.iter().map(|_| None).collect(),
+                    indices,
+                };
+                self.next_sequential(states, id)
+            }
+            (CompiledNode::Sequential { children, .. }, Visit::Resume) => {
+                let FlatState::Sequential { count, indices, .. } = &states[id] else {
+                    panic!("Invalid state.");
+                };
+                Action::Call(children[indices[*count]], Visit::Resume)
+            }
+            (CompiledNode::Parallel { children, .. }, Visit::Begin) => {
+                states[id] = FlatState::Parallel {
+                    children_status: children.iter().map(|_| ChildStatus::Beginning).collect(),
+                    results: children.iter().map(|_| None).collect(),
+                    cursor: 0,
+                    running: 0,
+                };
+                self.enter(world, entity, states, id, Visit::Resume)
+            }
+            (CompiledNode::Parallel { .. }, Visit::Resume) => {
+                if let Some(result) = self.parallel_result(states, id) {
+                    self.exit(world, entity, states, id);
+                    return Action::Return(Some(result));
+                }
+                let FlatState::Parallel {
+                    children_status,
+                    cursor,
+                    running,
+                    ..
+                } = &mut states[id]
+                else {
+                    panic!("Invalid state.");
+                };
+                // Children begin in order, so the waiting ones come after the running ones.
+                *running = children_status
+                    .iter()
+                    .filter(|status| matches!(status, ChildStatus::Pending))
+                    .count();
+                *cursor = 0;
+                self.next_parallel(world, entity, states, id)
+            }
+            (CompiledNode::Converter { child, .. }, visit) => {
+                states[id] = FlatState::Converter;
+                Action::Call(*child, visit)
+            }
+            (CompiledNode::Loop { .. }, Visit::Begin) => {
+                states[id] = FlatState::Loop {
+                    loop_state: LoopState::default(),
+                    backoff: None,
+                    running: false,
+                };
+                self.next_loop(world, entity, states, id)
+            }
+            (CompiledNode::Loop { child, .. }, Visit::Resume) => {
+                let FlatState::Loop { running, .. } = &states[id] else {
+                    panic!("Invalid state.");
+                };
+                if *running {
+                    Action::Call(*child, Visit::Resume)
+                } else {
+                    // Waiting for the backoff.
+                    self.next_loop(world, entity, states, id)
+                }
+            }
+        }
+    }
+
+    /// Continues the node with the result of the child it called.
+    fn proceed(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        states: &mut [FlatState],
+        id: NodeId,
+        child_result: Option<NodeResult>,
+    ) -> Action {
+        match &self.nodes[id] {
+            CompiledNode::Leaf(_) => panic!("Leaf has no children."),
+            CompiledNode::Sequential {
+                result_strategy, ..
+            } => {
+                // Pending child keeps its state in place.
+                let Some(child_result) = child_result else {
+                    return Action::Return(None);
+                };
+                let FlatState::Sequential { count, results, .. } = &mut states[id] else {
+                    panic!("Invalid state.");
+                };
+                results[*count] = Some(child_result);
+                *count += 1;
+                if let Some(result) = result_strategy.construct(results) {
+                    states[id] = FlatState::Idle;
+                    return Action::Return(Some(result));
+                }
+                self.next_sequential(states, id)
+            }
+            CompiledNode::Parallel { .. } => {
+                let FlatState::Parallel {
+                    children_status,
+                    results,
+                    cursor,
+                    running,
+                } = &mut states[id]
+                else {
+                    panic!("Invalid state.");
+                };
+                results[*cursor] = child_result;
+                children_status[*cursor] = match child_result {
+                    None => {
+                        *running += 1;
+                        ChildStatus::Pending
+                    }
+                    Some(_) => ChildStatus::Complete,
+                };
+                *cursor += 1;
+                self.next_parallel(world, entity, states, id)
+            }
+            CompiledNode::Converter { converter, .. } => {
+                let Some(child_result) = child_result else {
+                    return Action::Return(None);
+                };
+                states[id] = FlatState::Idle;
+                Action::Return(Some(converter.convert(child_result)))
+            }
+            CompiledNode::Loop { .. } => {
+                let Some(child_result) = child_result else {
+                    return Action::Return(None);
+                };
+                let FlatState::Loop {
+                    loop_state,
+                    running,
+                    ..
+                } = &mut states[id]
+                else {
+                    panic!("Invalid state.");
+                };
+                *loop_state = loop_state.update(child_result);
+                *running = false;
+                self.next_loop(world, entity, states, id)
+            }
+        }
+    }
+
+    /// Begins the next child of the sequential node, or completes after all of them.
+    fn next_sequential(&self, states: &mut [FlatState], id: NodeId) -> Action {
+        let CompiledNode::Sequential {
+            children,
+            result_strategy,
+            ..
+        } = &self.nodes[id]
+        else {
+            panic!("Invalid node.");
+        };
+        let FlatState::Sequential {
+            count,
+            indices,
+            results,
+        } = &mut states[id]
+        else {
+            panic!("Invalid state.");
+        };
+        if let Some(&index) = indices.get(*count) {
+            return Action::Call(children[index], Visit::Begin);
+        }
+        // All the nodes are completed.
+        let Some(result) = result_strategy.construct(results) else {
+            panic!("Result constructor returned None on the end.");
+        };
+        states[id] = FlatState::Idle;
+        Action::Return(Some(result))
+    }
+
+    /// Runs the children of the parallel node from the cursor, then checks the result.
+    fn next_parallel(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        states: &mut [FlatState],
+        id: NodeId,
+    ) -> Action {
+        let CompiledNode::Parallel {
+            children, limit, ..
+        } = &self.nodes[id]
+        else {
+            panic!("Invalid node.");
+        };
+        let FlatState::Parallel {
+            children_status,
+            cursor,
+            running,
+            ..
+        } = &mut states[id]
+        else {
+            panic!("Invalid state.");
+        };
+        while let Some(status) = children_status.get(*cursor) {
+            match status {
+                ChildStatus::Beginning if limit.is_some_and(|limit| limit.get() <= *running) => {}
+                ChildStatus::Beginning => return Action::Call(children[*cursor], Visit::Begin),
+                ChildStatus::Pending => {
+                    *running -= 1;
+                    return Action::Call(children[*cursor], Visit::Resume);
+                }
+                ChildStatus::Complete => {}
+            }
+            *cursor += 1;
+        }
+        let result = self.parallel_result(states, id);
+        if result.is_some() {
+            self.exit(world, entity, states, id);
+        }
+        Action::Return(result)
+    }
+
+    /// Begins the next iteration of the loop node, unless the condition fails or the backoff is waiting.
+    fn next_loop(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        states: &mut [FlatState],
+        id: NodeId,
+    ) -> Action {
+        let CompiledNode::Loop {
+            child,
+            checker_builder,
+            backoff,
+            checker_runtime,
+        } = &self.nodes[id]
+        else {
+            panic!("Invalid node.");
+        };
+        let FlatState::Loop {
+            loop_state,
+            backoff: stopwatch,
+            running,
+        } = &mut states[id]
+        else {
+            panic!("Invalid state.");
+        };
+        let loop_state = *loop_state;
+        let check = |world: &mut World| {
+            run_loop_checker(
+                checker_runtime,
+                checker_builder.as_ref(),
+                world,
+                entity,
+                loop_state,
+            )
+        };
+        match LoopStep::next(check, *backoff, world, loop_state, stopwatch) {
+            LoopStep::Begin => {
+                *running = true;
+                Action::Call(*child, Visit::Begin)
+            }
+            LoopStep::Wait => Action::Return(None),
+            LoopStep::Complete(result) => {
+                states[id] = FlatState::Idle;
+                Action::Return(Some(result))
+            }
+        }
+    }
+
+    fn parallel_result(&self, states: &[FlatState], id: NodeId) -> Option<NodeResult> {
+        let CompiledNode::Parallel {
+            result_strategy, ..
+        } = &self.nodes[id]
+        else {
+            panic!("Invalid node.");
+        };
+        let FlatState::Parallel { results, .. } = &states[id] else {
+            panic!("Invalid state.");
+        };
+        result_strategy.construct(results)
+    }
+
+    /// Force exits the running nodes under the node, in order.
+    fn exit(&self, world: &mut World, entity: Entity, states: &mut [FlatState], id: NodeId) {
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            match (&self.nodes[id], std::mem::take(&mut states[id])) {
+                (CompiledNode::Leaf(node), FlatState::Leaf(state)) => {
+                    node.force_exit(world, entity, state)
+                }
+                (
+                    CompiledNode::Sequential { children, .. },
+                    FlatState::Sequential { count, indices, .. },
+                ) => {
+                    if let Some(&index) = indices.get(count) {
+                        stack.push(children[index]);
+                    }
+                }
+                (
+                    CompiledNode::Parallel { children, .. },
+                    FlatState::Parallel {
+                        children_status, ..
+                    },
+                ) => {
+                    stack.extend(
+                        children
+                            .iter()
+                            .zip(children_status)
+                            .filter(|(_, status)| matches!(status, ChildStatus::Pending))
+                            .map(|(&child, _)| child)
+                            .rev(),
+                    );
+                }
+                (CompiledNode::Converter { child, .. }, FlatState::Converter) => {
+                    stack.push(*child);
+                }
+                (CompiledNode::Loop { child, .. }, FlatState::Loop { running: true, .. }) => {
+                    stack.push(*child);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl Node for CompiledTree {
+    fn begin(&self, world: &mut World, entity: Entity) -> NodeStatus {
+        let mut state = CompiledTreeState {
+            states: self.nodes.iter().map(|_| FlatState::Idle).collect(),
+            stack: Vec::new(),
+            path: Vec::new(),
+        };
+        match self.run(world, entity, &mut state, Visit::Begin) {
+            Some(result) => NodeStatus::Complete(result),
+            None => NodeStatus::Pending(Box::new(state)),
+        }
+    }
+
+    fn resume(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        mut state: Box<dyn NodeState>,
+    ) -> NodeStatus {
+        let result = self.resume_in_place(world, entity, &mut state);
+        NodeStatus::from_resumed(result, state)
+    }
+
+    fn resume_in_place(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        state: &mut Box<dyn NodeState>,
+    ) -> Option<NodeResult> {
+        let state = Self::downcast_mut(state).expect("Invalid state.");
+        self.run(world, entity, state, Visit::Resume)
+    }
+
+    fn force_exit(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) {
+        let mut state = Self::downcast(state).expect("Invalid state.");
+        self.exit(world, entity, &mut state.states, ROOT);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Visit {
+    Begin,
+    Resume,
+}
+
+/// Next step of [`CompiledTree::run`].
+enum Action {
+    /// Begins or resumes the child, then proceeds the current node with its result.
+    Call(NodeId, Visit),
+    /// Returns the result to the parent, `None` while pending.
+    Return(Option<NodeResult>),
+}
+
+/// State of each node in [`CompiledTree`], idle unless running.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default)]
+enum FlatState {
+    #[default]
+    Idle,
+    Leaf(Box<dyn NodeState>),
+    Sequential {
+        count: usize,
+        indices: Vec<usize>,
+        results: Vec<Option<NodeResult>>,
+    },
+    Parallel {
+        children_status: Vec<ChildStatus>,
+        /// Results of the children, kept along the status to pass to the strategy.
+        results: Vec<Option<NodeResult>>,
+        /// Child to run next in the current update.
+        cursor: usize,
+        running: usize,
+    },
+    Converter,
+    Loop {
+        loop_state: LoopState,
+        /// Measures the backoff, while waiting before the next iteration.
+        backoff: Option<Stopwatch>,
+        /// Whether the child is running, rather than waiting for the backoff.
+        running: bool,
+    },
+}
+
+/// Status of the child of parallel node, the state of the pending child is held by the child itself.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy)]
+enum ChildStatus {
+    Beginning,
+    Pending,
+    Complete,
+}
+
+/// State for [`CompiledTree`]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(NodeState, Debug)]
+struct CompiledTreeState {
+    states: Vec<FlatState>,
+    /// Nodes waiting for the child, reused over the updates.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    stack: Vec<NodeId>,
+    /// Nodes from the root to the first leaf or parallel node running, empty to resume from the root.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    path: Vec<NodeId>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tester_util::prelude::*;
+
+    fn build() -> impl Node {
+        Sequence::new(vec![
+            Box::new(Join::new(vec![
+                Box::new(TesterTask0::new(2, NodeResult::Success)),
+                Box::new(Invert::new(TesterTask1::new(1, NodeResult::Failure))),
+            ])),
+            Box::new(Selector::new(vec![
+                Box::new(TesterTask2::new(1, NodeResult::Failure)),
+                Box::new(TesterTask3::new(1, NodeResult::Success)),
+            ])),
+            Box::new(ParallelOr::new(vec![
+                Box::new(TesterTask0::new(1, NodeResult::Success)),
+                Box::new(TesterTask1::new(3, NodeResult::Success)),
+            ])),
+        ])
+    }
+
+    /// Runs the tree, then returns the sorted log and the status.
+    fn run(node: impl Node, updates: usize) -> (Vec<(u32, u32, u32)>, Option<NodeResult>, bool) {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let tree = BehaviorTree::from_node(
+            node,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        for _ in 0..updates {
+            app.update();
+        }
+        // Order of the log entries within same frame may change.
+        let mut log: Vec<_> = app
+            .world()
+            .resource::<TestLog>()
+            .log
+            .iter()
+            .map(|entry| (entry.task_id, entry.updated_count, entry.frame))
+            .collect();
+        log.sort();
+        let result = app.world().get::<TreeStatus>(entity).unwrap().result();
+        let exited = app.world().get::<TesterComponent<1>>(entity).is_none();
+        (log, result, exited)
+    }
+
+    #[test]
+    fn test_same_as_nested() {
+        let nested = run(build(), 8);
+        let compiled = run(CompiledTree::new(build()), 8);
+        assert!(
+            nested.1 == Some(NodeResult::Success) && nested.2,
+            "Nested tree should complete. found: {:?}",
+            nested
+        );
+        assert!(
+            compiled == nested,
+            "CompiledTree should run the same as the nested tree. expected: {:?}, found: {:?}",
+            nested,
+            compiled
+        );
+    }
+
+    #[test]
+    fn test_same_as_nested_sequences() {
+        let build = || {
+            Sequence::new(vec![
+                Box::new(Sequence::new(vec![
+                    Box::new(TesterTask0::new(2, NodeResult::Success)),
+                    Box::new(Invert::new(TesterTask1::new(2, NodeResult::Failure))),
+                ])),
+                Box::new(Selector::new(vec![Box::new(Sequence::new(vec![
+                    Box::new(TesterTask2::new(1, NodeResult::Success)),
+                    Box::new(TesterTask3::new(2, NodeResult::Success)),
+                ]))])),
+            ])
+        };
+        let nested = run(build(), 10);
+        let compiled = run(CompiledTree::new(build()), 10);
+        assert!(
+            nested.1 == Some(NodeResult::Success),
+            "Nested tree should complete. found: {:?}",
+            nested
+        );
+        assert!(
+            compiled == nested,
+            "CompiledTree should resume the nested sequences the same. expected: {:?}, found: {:?}",
+            nested,
+            compiled
+        );
+    }
+
+    #[test]
+    fn test_same_as_nested_loop() {
+        let build = || {
+            Repeat::new(
+                Sequence::new(vec![
+                    Box::new(TesterTask0::new(1, NodeResult::Success)),
+                    Box::new(TesterTask1::new(2, NodeResult::Success)),
+                ]),
+                3,
+            )
+        };
+        let nested = run(build(), 12);
+        let compiled = run(CompiledTree::new(build()), 12);
+        assert!(
+            nested.1 == Some(NodeResult::Success),
+            "Nested tree should complete. found: {:?}",
+            nested
+        );
+        assert!(
+            compiled == nested,
+            "CompiledTree should run the loop the same. expected: {:?}, found: {:?}",
+            nested,
+            compiled
+        );
+    }
+
+    #[test]
+    fn test_deep_nesting() {
+        let node = (0..10_000).fold(
+            Sequence::new(vec![Box::new(TesterTask0::new(1, NodeResult::Success))]),
+            |node, _| Sequence::new(vec![Box::new(node)]),
+        );
+        let (log, result, _) = run(CompiledTree::new(node), 4);
+        assert!(
+            log == vec![(0, 0, 1)] && result == Some(NodeResult::Success),
+            "CompiledTree should run deeply nested nodes. found: {:?}, {:?}",
+            log,
+            result
+        );
+    }
+
+    #[test]
+    fn test_force_exit() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let node = CompiledTree::new(Join::new(vec![
+            Box::new(TesterTask0::new(10, NodeResult::Success)),
+            Box::new(Sequence::new(vec![Box::new(TesterTask1::new(
+                10,
+                NodeResult::Success,
+            ))])),
+        ]));
+        let tree = BehaviorTree::from_node(
+            node,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        app.update();
+        app.update();
+        BehaviorTree::abort(app.world_mut(), entity);
+        app.update();
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_none()
+                && app.world().get::<TesterComponent<1>>(entity).is_none(),
+            "CompiledTree should force exit the running nodes."
+        );
+    }
+}
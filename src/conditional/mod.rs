@@ -1,6 +1,6 @@
 //! Nodes that depends on the condition of the bevy world().
 
-use std::{fmt::Debug, sync::Mutex};
+use std::{fmt::Debug, sync::Mutex, time::Duration};
 
 use bevy::ecs::{
     entity::Entity,
@@ -8,13 +8,18 @@ use bevy::ecs::{
     world::World,
 };
 
-use crate::node::prelude::*;
+use crate::{
+    compiled::FlatNode,
+    node::{VacantState, prelude::*},
+    timer::{Period, Stopwatch, TimeSource},
+};
 
 pub mod variants;
 
 pub mod prelude {
     pub use super::{
-        CheckIf, ConditionalLoop, ElseFreeze, LoopCondChecker, LoopState, variants::prelude::*,
+        Backoff, CheckIf, ConditionalLoop, ElseFreeze, Guard, LoopCondChecker, LoopState,
+        variants::prelude::*,
     };
 }
 
@@ -30,6 +35,11 @@ pub trait LoopCondCheckerBuilder: 'static + Debug + Send + Sync {
 pub struct LoopCountCondCheckerBuilder {
     max_count: usize,
 }
+impl LoopCountCondCheckerBuilder {
+    pub fn new(max_count: usize) -> Self {
+        Self { max_count }
+    }
+}
 #[cfg_attr(feature = "serde", typetag::serde)]
 impl LoopCondCheckerBuilder for LoopCountCondCheckerBuilder {
     fn build(&self) -> Box<LoopCondChecker> {
@@ -40,6 +50,42 @@ impl LoopCondCheckerBuilder for LoopCountCondCheckerBuilder {
     }
 }
 
+/// Delays before the second and later iterations of [`ConditionalLoop`].
+/// The delay starts from `initial`, multiplied by `multiplier` on each iteration up to `max`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Backoff {
+    pub initial: Duration,
+    pub multiplier: f32,
+    pub max: Duration,
+    pub time: TimeSource,
+}
+impl Backoff {
+    pub fn fixed(delay: Duration) -> Self {
+        Self::exponential(delay, 1.0, delay)
+    }
+    pub fn exponential(initial: Duration, multiplier: f32, max: Duration) -> Self {
+        Self {
+            initial,
+            multiplier,
+            max,
+            time: TimeSource::default(),
+        }
+    }
+    /// Reads the given clock rather than default [`TimeSource::Virtual`].
+    pub fn with_time(mut self, time: TimeSource) -> Self {
+        self.time = time;
+        self
+    }
+    /// Delay before the iteration, after the `count` iterations completed.
+    /// Negative or NaN multipliers are taken as 0.
+    pub(crate) fn delay(&self, count: usize) -> Duration {
+        let exponent = count.saturating_sub(1).min(i32::MAX as usize) as i32;
+        let factor = self.multiplier.max(0.0).powi(exponent).min(u32::MAX as f32);
+        self.initial.mul_f32(factor).min(self.max)
+    }
+}
+
 /// Node for conditional loop.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug)]
@@ -47,6 +93,8 @@ impl LoopCondCheckerBuilder for LoopCountCondCheckerBuilder {
 pub struct ConditionalLoop {
     child: Box<dyn Node>,
     checker_builder: Box<dyn LoopCondCheckerBuilder>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    backoff: Option<Backoff>,
     #[cfg_attr(feature = "serde", serde(skip))]
     checker_runtime: Mutex<Option<Box<LoopCondChecker>>>,
 }
@@ -55,83 +103,173 @@ impl ConditionalLoop {
         Self {
             child: Box::new(child),
             checker_builder: Box::new(checker_builder),
+            backoff: None,
             checker_runtime: Mutex::new(None),
         }
     }
+    /// Waits between the iterations.
+    pub fn with_backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = Some(backoff);
+        self
+    }
     pub fn check(&self, world: &mut World, entity: Entity, loop_state: LoopState) -> bool {
-        let mut checker_lock = self.checker_runtime.lock().expect("Failed to lock.");
-        if checker_lock.is_none() {
-            let mut new_checker = self.checker_builder.build();
-            new_checker.initialize(world);
-            *checker_lock = Some(new_checker);
+        run_loop_checker(
+            &self.checker_runtime,
+            self.checker_builder.as_ref(),
+            world,
+            entity,
+            loop_state,
+        )
+    }
+}
+
+/// Runs the checker, built on the first use.
+pub(crate) fn run_loop_checker(
+    runtime: &Mutex<Option<Box<LoopCondChecker>>>,
+    builder: &dyn LoopCondCheckerBuilder,
+    world: &mut World,
+    entity: Entity,
+    loop_state: LoopState,
+) -> bool {
+    let mut checker_lock = runtime.lock().expect("Failed to lock.");
+    if checker_lock.is_none() {
+        let mut new_checker = builder.build();
+        new_checker.initialize(world);
+        *checker_lock = Some(new_checker);
+    }
+    checker_lock
+        .as_mut()
+        .expect("Checker not initialized.")
+        .run((entity, loop_state), world)
+        .expect("Failed to run checker system.")
+}
+
+/// What [`ConditionalLoop`] does before the next iteration.
+pub(crate) enum LoopStep {
+    Begin,
+    Wait,
+    Complete(NodeResult),
+}
+impl LoopStep {
+    /// Checks the condition once, then waits for the backoff measured by `stopwatch`.
+    pub(crate) fn next(
+        check: impl FnOnce(&mut World) -> bool,
+        backoff: Option<Backoff>,
+        world: &mut World,
+        loop_state: LoopState,
+        stopwatch: &mut Option<Stopwatch>,
+    ) -> Self {
+        if stopwatch.is_none() && !check(world) {
+            return Self::Complete(loop_state.last_result.unwrap_or(NodeResult::Failure));
         }
-        checker_lock
-            .as_mut()
-            .expect("Checker not initialized.")
-            .run((entity, loop_state), world)
-            .expect("Failed to run checker system.")
+        if let Some(backoff) = backoff.filter(|_| 0 < loop_state.count) {
+            let delay = backoff.delay(loop_state.count);
+            let watch = match stopwatch {
+                Some(watch) => {
+                    watch.tick(backoff.time, world);
+                    watch
+                }
+                None => stopwatch.insert(Stopwatch::start(backoff.time, world)),
+            };
+            if !watch.reached(Period::Time(delay)) {
+                return Self::Wait;
+            }
+            *stopwatch = None;
+        }
+        Self::Begin
     }
 }
 #[cfg_attr(feature = "serde", typetag::serde)]
 impl Node for ConditionalLoop {
     fn begin(&self, world: &mut World, entity: Entity) -> NodeStatus {
         let state = ConditionalLoopState {
-            loop_state: LoopState {
-                count: 0,
-                last_result: None,
-            },
+            loop_state: LoopState::default(),
             child_status: NodeStatus::Beginning,
+            backoff: None,
         };
         self.resume(world, entity, Box::new(state))
     }
 
-    fn resume(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) -> NodeStatus {
-        let state = Self::downcast(state).expect("Invalid state type.");
-        let state = match state.child_status {
-            NodeStatus::Beginning => {
-                if !self.check(world, entity, state.loop_state) {
-                    return NodeStatus::Complete(
-                        state.loop_state.last_result.unwrap_or(NodeResult::Failure),
-                    );
+    fn resume(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        mut state: Box<dyn NodeState>,
+    ) -> NodeStatus {
+        let result = self.resume_in_place(world, entity, &mut state);
+        NodeStatus::from_resumed(result, state)
+    }
+
+    fn resume_in_place(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        state: &mut Box<dyn NodeState>,
+    ) -> Option<NodeResult> {
+        let state = Self::downcast_mut(state).expect("Invalid state type.");
+        loop {
+            match &mut state.child_status {
+                NodeStatus::Beginning => {
+                    match LoopStep::next(
+                        |world| self.check(world, entity, state.loop_state),
+                        self.backoff,
+                        world,
+                        state.loop_state,
+                        &mut state.backoff,
+                    ) {
+                        LoopStep::Begin => {}
+                        LoopStep::Wait => return None,
+                        LoopStep::Complete(result) => return Some(result),
+                    }
+                    state.child_status = self.child.begin(world, entity);
+                    if matches!(state.child_status, NodeStatus::Pending(_)) {
+                        return None;
+                    }
                 }
-                ConditionalLoopState {
-                    loop_state: state.loop_state,
-                    child_status: self.child.begin(world, entity),
+                NodeStatus::Pending(child_state) => {
+                    let result = self.child.resume_in_place(world, entity, child_state)?;
+                    state.child_status = NodeStatus::Complete(result);
+                }
+                &mut NodeStatus::Complete(result) => {
+                    state.loop_state = state.loop_state.update(result);
+                    state.child_status = NodeStatus::Beginning;
                 }
             }
-            NodeStatus::Pending(child_state) => ConditionalLoopState {
-                loop_state: state.loop_state,
-                child_status: self.child.resume(world, entity, child_state),
-            },
-            NodeStatus::Complete(result) => ConditionalLoopState {
-                loop_state: state.loop_state.update(result),
-                child_status: NodeStatus::Beginning,
-            },
-        };
-        match &state.child_status {
-            &NodeStatus::Beginning => self.resume(world, entity, Box::new(state)),
-            &NodeStatus::Complete(_) => self.resume(world, entity, Box::new(state)),
-            &NodeStatus::Pending(_) => NodeStatus::Pending(Box::new(state)),
         }
     }
 
     fn force_exit(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) {
         let state = Self::downcast(state).expect("Invalid state type.");
-        match state.child_status {
-            NodeStatus::Pending(child_state) => self.child.force_exit(world, entity, child_state),
-            _ => {}
+        if let NodeStatus::Pending(child_state) = state.child_status {
+            self.child.force_exit(world, entity, child_state);
+        }
+    }
+
+    fn flatten(self: Box<Self>) -> FlatNode {
+        FlatNode::Loop {
+            child: self.child,
+            checker_builder: self.checker_builder,
+            backoff: self.backoff,
         }
     }
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(NodeState, Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(NodeState, Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub struct LoopState {
     count: usize,
     last_result: Option<NodeResult>,
 }
 impl LoopState {
-    fn update(self, result: NodeResult) -> Self {
+    /// Count of the completed iterations.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+    /// Result of the last iteration, None before the first completion.
+    pub fn last_result(&self) -> Option<NodeResult> {
+        self.last_result
+    }
+    pub(crate) fn update(self, result: NodeResult) -> Self {
         Self {
             count: self.count + 1,
             last_result: Some(result),
@@ -145,6 +283,8 @@ impl LoopState {
 struct ConditionalLoopState {
     loop_state: LoopState,
     child_status: NodeStatus,
+    /// Measures the backoff, while waiting before the next iteration.
+    backoff: Option<Stopwatch>,
 }
 
 pub type CondChecker = dyn System<In = In<Entity>, Out = bool>;
@@ -252,34 +392,50 @@ impl Node for ElseFreeze {
         )
     }
 
-    fn resume(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) -> NodeStatus {
-        let state = Self::downcast(state).expect("Invalid state.");
+    fn resume(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        mut state: Box<dyn NodeState>,
+    ) -> NodeStatus {
+        let result = self.resume_in_place(world, entity, &mut state);
+        NodeStatus::from_resumed(result, state)
+    }
+
+    fn resume_in_place(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        state: &mut Box<dyn NodeState>,
+    ) -> Option<NodeResult> {
+        let state = Self::downcast_mut(state).expect("Invalid state.");
         if !self.check(world, entity) {
-            return NodeStatus::Pending(Box::new(state));
+            return None;
         }
-        let child_status = match state.child_status {
-            NodeStatus::Beginning => self.child.begin(world, entity),
-            NodeStatus::Pending(child_state) => self.child.resume(world, entity, child_state),
-            NodeStatus::Complete(_) => {
-                panic!("Invalid child status.")
+        match &mut state.child_status {
+            NodeStatus::Beginning => match self.child.begin(world, entity) {
+                NodeStatus::Beginning => {
+                    panic!("Invalid child status.")
+                }
+                child_status => {
+                    let result = child_status.result();
+                    state.child_status = child_status;
+                    result
+                }
+            },
+            NodeStatus::Pending(child_state) => {
+                self.child.resume_in_place(world, entity, child_state)
             }
-        };
-        match &child_status {
-            NodeStatus::Beginning => {
+            NodeStatus::Complete(_) => {
                 panic!("Invalid child status.")
             }
-            NodeStatus::Pending(_) => {
-                NodeStatus::Pending(Box::new(ElseFreezeState { child_status }))
-            }
-            NodeStatus::Complete(_) => child_status,
         }
     }
 
     fn force_exit(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) {
         let state = Self::downcast(state).expect("Invalid state.");
-        match state.child_status {
-            NodeStatus::Pending(child_state) => self.child.force_exit(world, entity, child_state),
-            _ => {}
+        if let NodeStatus::Pending(child_state) = state.child_status {
+            self.child.force_exit(world, entity, child_state);
         }
     }
 }
@@ -291,6 +447,95 @@ struct ElseFreezeState {
     child_status: NodeStatus,
 }
 
+/// Node that runs the child while the condition is matched, checking on every update.
+/// Force exits the child and returns Failure when the condition is not matched.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+#[with_state(GuardState)]
+pub struct Guard {
+    child: Box<dyn Node>,
+    checker_builder: Box<dyn CondCheckerBuilder>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    checker_runtime: Mutex<Option<Box<CondChecker>>>,
+}
+impl Guard {
+    pub fn new(child: impl Node, checker_builder: impl CondCheckerBuilder) -> Self {
+        Self {
+            child: Box::new(child),
+            checker_builder: Box::new(checker_builder),
+            checker_runtime: Mutex::new(None),
+        }
+    }
+    fn check(&self, world: &mut World, entity: Entity) -> bool {
+        let mut checker_lock = self.checker_runtime.lock().expect("Failed to lock.");
+        if checker_lock.is_none() {
+            let mut new_checker = self.checker_builder.build();
+            new_checker.initialize(world);
+            *checker_lock = Some(new_checker);
+        }
+        checker_lock
+            .as_mut()
+            .expect("Checker not initialized.")
+            .run(entity, world)
+            .expect("Failed to run checker system.")
+    }
+    fn wrap(child_status: NodeStatus) -> NodeStatus {
+        match child_status {
+            NodeStatus::Pending(child_state) => {
+                NodeStatus::Pending(Box::new(GuardState { child_state }))
+            }
+            _ => child_status,
+        }
+    }
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl Node for Guard {
+    fn begin(&self, world: &mut World, entity: Entity) -> NodeStatus {
+        if !self.check(world, entity) {
+            return NodeStatus::Complete(NodeResult::Failure);
+        }
+        Self::wrap(self.child.begin(world, entity))
+    }
+
+    fn resume(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        mut state: Box<dyn NodeState>,
+    ) -> NodeStatus {
+        let result = self.resume_in_place(world, entity, &mut state);
+        NodeStatus::from_resumed(result, state)
+    }
+
+    fn resume_in_place(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        state: &mut Box<dyn NodeState>,
+    ) -> Option<NodeResult> {
+        let state = Self::downcast_mut(state).expect("Invalid state.");
+        if !self.check(world, entity) {
+            let child_state = VacantState::take(&mut state.child_state);
+            self.child.force_exit(world, entity, child_state);
+            return Some(NodeResult::Failure);
+        }
+        self.child
+            .resume_in_place(world, entity, &mut state.child_state)
+    }
+
+    fn force_exit(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) {
+        let state = Self::downcast(state).expect("Invalid state.");
+        self.child.force_exit(world, entity, state.child_state);
+    }
+}
+
+/// State for [`Guard`]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(NodeState, Debug)]
+struct GuardState {
+    child_state: Box<dyn NodeState>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -339,6 +584,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_backoff_invalid_multiplier() {
+        for multiplier in [-2.0, f32::NAN] {
+            let backoff = Backoff::exponential(
+                Duration::from_millis(100),
+                multiplier,
+                Duration::from_secs(1),
+            );
+            let found: Vec<_> = (2..=4).map(|count| backoff.delay(count)).collect();
+            assert!(
+                found.iter().all(|delay| *delay == Duration::ZERO),
+                "Invalid multiplier should be taken as 0. found: {:?}",
+                found
+            );
+        }
+    }
+
     #[test]
     fn test_repeat_count() {
         let mut app = App::new();
@@ -395,10 +657,10 @@ mod tests {
         app.update();
         let tree_status = app.world().get::<TreeStatus>(entity);
         assert!(
-            match tree_status {
-                Some(&TreeStatus(NodeStatus::Complete(NodeResult::Failure))) => true,
-                _ => false,
-            },
+            matches!(
+                tree_status,
+                Some(&TreeStatus(NodeStatus::Complete(NodeResult::Failure)))
+            ),
             "CheckIf should match the result."
         );
     }
@@ -417,10 +679,10 @@ mod tests {
         app.update();
         let tree_status = app.world().get::<TreeStatus>(entity);
         assert!(
-            match tree_status {
-                Some(&TreeStatus(NodeStatus::Complete(NodeResult::Success))) => true,
-                _ => false,
-            },
+            matches!(
+                tree_status,
+                Some(&TreeStatus(NodeStatus::Complete(NodeResult::Success)))
+            ),
             "CheckIf should match the result."
         );
     }
@@ -486,4 +748,38 @@ mod tests {
             found
         );
     }
+
+    #[test]
+    fn test_guard() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let task = TesterTask0::new(5, NodeResult::Success);
+        let guard = Guard::new(task, TestMarkerExistsCondCheckerBuilder);
+        let tree = BehaviorTree::from_node(
+            guard,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn((tree, TestMarker)).id();
+        app.update();
+        app.update(); // 0
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_some(),
+            "Guard should run the child while the condition is matched."
+        );
+        app.world_mut().entity_mut(entity).remove::<TestMarker>();
+        app.update(); // abort
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_none(),
+            "Guard should force exit the child when the condition is not matched."
+        );
+        let tree_status = app.world().get::<TreeStatus>(entity);
+        assert!(
+            matches!(
+                tree_status,
+                Some(&TreeStatus(NodeStatus::Complete(NodeResult::Failure)))
+            ),
+            "Guard should complete with Failure. found: {:?}",
+            tree_status
+        );
+    }
 }
@@ -5,13 +5,14 @@ use bevy::ecs::{
 };
 
 use super::{
-    CondCheckerBuilder, ConditionalLoop, LoopCondChecker, LoopCondCheckerBuilder, LoopState,
+    Backoff, CondCheckerBuilder, ConditionalLoop, LoopCondChecker, LoopCondCheckerBuilder,
+    LoopCountCondCheckerBuilder, LoopState,
 };
 use crate as bevior_tree;
 use crate::node::prelude::*;
 
 pub mod prelude {
-    pub use super::{Conditional, InfiniteLoop};
+    pub use super::{Conditional, InfiniteLoop, Repeat, RepeatUntil, RepeatWhile, Retry};
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -81,6 +82,129 @@ impl InfiniteLoop {
     }
 }
 
+/// Node that runs the child the given times, then returns the last result.
+#[delegate_node(delegate)]
+pub struct Repeat {
+    delegate: ConditionalLoop,
+}
+impl Repeat {
+    pub fn new(child: impl Node, count: usize) -> Self {
+        Self {
+            delegate: ConditionalLoop::new(child, LoopCountCondCheckerBuilder::new(count)),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+pub struct UntilResultLoopCondCheckerBuilder {
+    result: NodeResult,
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl LoopCondCheckerBuilder for UntilResultLoopCondCheckerBuilder {
+    fn build(&self) -> Box<LoopCondChecker> {
+        let result = self.result;
+        Box::new(IntoSystem::into_system(
+            move |In((_, loop_state)): In<(Entity, LoopState)>| {
+                loop_state.last_result() != Some(result)
+            },
+        ))
+    }
+}
+
+/// Node that runs the child until it returns the given result.
+#[delegate_node(delegate)]
+pub struct RepeatUntil {
+    delegate: ConditionalLoop,
+}
+impl RepeatUntil {
+    pub fn new(child: impl Node, result: NodeResult) -> Self {
+        Self {
+            delegate: ConditionalLoop::new(child, UntilResultLoopCondCheckerBuilder { result }),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+pub struct WhileLoopCondCheckerBuilder {
+    checker_builder: Box<dyn CondCheckerBuilder>,
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl LoopCondCheckerBuilder for WhileLoopCondCheckerBuilder {
+    fn build(&self) -> Box<LoopCondChecker> {
+        let mut checker = self.checker_builder.build();
+        let mut initialized = false;
+        Box::new(IntoSystem::into_system(
+            move |In((entity, _)): In<(Entity, LoopState)>, world: &mut World| {
+                if !initialized {
+                    checker.initialize(world);
+                    initialized = true;
+                }
+                checker.run(entity, world).expect("Failed to run checker")
+            },
+        ))
+    }
+}
+
+/// Node that runs the child while the condition is matched, checking before each iteration.
+/// Returns the last result, or Failure if the child never ran.
+#[delegate_node(delegate)]
+pub struct RepeatWhile {
+    delegate: ConditionalLoop,
+}
+impl RepeatWhile {
+    pub fn new(child: impl Node, checker_builder: impl CondCheckerBuilder) -> Self {
+        Self {
+            delegate: ConditionalLoop::new(
+                child,
+                WhileLoopCondCheckerBuilder {
+                    checker_builder: Box::new(checker_builder),
+                },
+            ),
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+pub struct RetryLoopCondCheckerBuilder {
+    max_retries: usize,
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl LoopCondCheckerBuilder for RetryLoopCondCheckerBuilder {
+    fn build(&self) -> Box<LoopCondChecker> {
+        let max_retries = self.max_retries;
+        Box::new(IntoSystem::into_system(
+            move |In((_, loop_state)): In<(Entity, LoopState)>| match loop_state.last_result() {
+                None => true,
+                Some(NodeResult::Failure) => loop_state.count() <= max_retries,
+                Some(NodeResult::Success) => false,
+            },
+        ))
+    }
+}
+
+/// Node that runs the child again on Failure, up to the given retries.
+/// Returns the last result.
+#[delegate_node(delegate)]
+pub struct Retry {
+    delegate: ConditionalLoop,
+}
+impl Retry {
+    pub fn new(child: impl Node, max_retries: usize) -> Self {
+        Self {
+            delegate: ConditionalLoop::new(child, RetryLoopCondCheckerBuilder { max_retries }),
+        }
+    }
+    /// Waits before each retry.
+    pub fn with_backoff(self, backoff: Backoff) -> Self {
+        Self {
+            delegate: self.delegate.with_backoff(backoff),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::{CondChecker, CondCheckerBuilder};
@@ -195,4 +319,125 @@ mod tests {
             found
         );
     }
+
+    fn run(app: &mut App, node: impl Node, updates: usize) -> Entity {
+        let tree = BehaviorTree::from_node(
+            node,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        for _ in 0..updates {
+            app.update();
+        }
+        entity
+    }
+
+    #[test]
+    fn test_repeat() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let repeat = Repeat::new(TesterTask0::new(1, NodeResult::Failure), 2);
+        let entity = run(&mut app, repeat, 4);
+        let found = log_frames(&app);
+        assert!(
+            found == vec![1, 2],
+            "Repeat should run the child the given times. found: {:?}",
+            found
+        );
+        let status = app.world().get::<TreeStatus>(entity).unwrap();
+        assert!(
+            status.result() == Some(NodeResult::Failure),
+            "Repeat should return the last result. found: {:?}",
+            status
+        );
+    }
+
+    #[test]
+    fn test_repeat_until() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let repeat = RepeatUntil::new(
+            TesterTask0::new(1, NodeResult::Success),
+            NodeResult::Success,
+        );
+        let entity = run(&mut app, repeat, 4);
+        let found = log_frames(&app);
+        assert!(
+            found == vec![1],
+            "RepeatUntil should stop on the result. found: {:?}",
+            found
+        );
+        let status = app.world().get::<TreeStatus>(entity).unwrap();
+        assert!(
+            status.result() == Some(NodeResult::Success),
+            "RepeatUntil should return the result. found: {:?}",
+            status
+        );
+    }
+
+    #[test]
+    fn test_repeat_while() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let repeat = RepeatWhile::new(
+            TesterTask0::new(1, NodeResult::Success),
+            TestMarkerExistsCondCheckerBuilder,
+        );
+        let entity = run(&mut app, repeat, 0);
+        app.world_mut().entity_mut(entity).insert(TestMarker);
+        app.update();
+        app.update(); // 0
+        app.update(); // 0
+        app.world_mut().entity_mut(entity).remove::<TestMarker>();
+        app.update(); // complete
+        app.update(); // nop
+        let found = log_frames(&app);
+        assert!(
+            found == vec![1, 2, 3],
+            "RepeatWhile should repeat while the condition is matched. found: {:?}",
+            found
+        );
+    }
+
+    #[test]
+    fn test_retry() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let retry = Retry::new(TesterTask0::new(1, NodeResult::Failure), 2);
+        let entity = run(&mut app, retry, 5);
+        let found = log_frames(&app);
+        assert!(
+            found == vec![1, 2, 3],
+            "Retry should run the child up to the retries on Failure. found: {:?}",
+            found
+        );
+        let status = app.world().get::<TreeStatus>(entity).unwrap();
+        assert!(
+            status.result() == Some(NodeResult::Failure),
+            "Retry should return the last result. found: {:?}",
+            status
+        );
+    }
+
+    #[test]
+    fn test_retry_backoff() {
+        use bevy::time::TimeUpdateStrategy;
+        use std::time::Duration;
+
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )));
+        let retry = Retry::new(TesterTask0::new(1, NodeResult::Failure), 2).with_backoff(
+            Backoff::exponential(Duration::from_millis(150), 2.0, Duration::from_secs(1)),
+        );
+        run(&mut app, retry, 12);
+        let found = log_frames(&app);
+        assert!(
+            found == vec![1, 4, 9],
+            "Retry should wait the growing backoff before each retry. found: {:?}",
+            found
+        );
+    }
 }
@@ -4,7 +4,7 @@ use std::fmt::Debug;
 
 use bevy::ecs::{entity::Entity, world::World};
 
-use crate::node::prelude::*;
+use crate::{compiled::FlatNode, node::prelude::*};
 
 pub mod variants;
 
@@ -46,7 +46,24 @@ impl Node for ResultConverter {
     fn resume(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) -> NodeStatus {
         self.convert(self.child.resume(world, entity, state))
     }
+    fn resume_in_place(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        state: &mut Box<dyn NodeState>,
+    ) -> Option<NodeResult> {
+        self.child
+            .resume_in_place(world, entity, state)
+            .map(|result| self.converter.convert(result))
+    }
     fn force_exit(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) {
         self.child.force_exit(world, entity, state)
     }
+
+    fn flatten(self: Box<Self>) -> FlatNode {
+        FlatNode::Converter {
+            child: self.child,
+            converter: self.converter,
+        }
+    }
 }
@@ -0,0 +1,126 @@
+//! Events on the lifecycle of the behavior trees.
+//! Each of them is triggered for observers, and also written as buffered message.
+
+use bevy::ecs::{entity::Entity, event::Event, message::Message, world::World};
+use bevy::prelude::EntityEvent;
+
+use crate::node::NodeResult;
+
+pub mod prelude {
+    pub use super::{TreeAborted, TreeCompleted, TreeRestarted, TreeStarted};
+}
+
+/// The root of the tree began.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(EntityEvent, Message, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TreeStarted {
+    pub entity: Entity,
+}
+
+/// The root of the tree completed with the result.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(EntityEvent, Message, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TreeCompleted {
+    pub entity: Entity,
+    pub result: NodeResult,
+}
+
+/// The running tree was force exited,
+///  by [`crate::BehaviorTree::abort`] or by removal, replacement or despawn of the tree.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(EntityEvent, Message, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TreeAborted {
+    pub entity: Entity,
+}
+
+/// The tree is going to begin again,
+///  by [`crate::RestartPolicy`] or by [`crate::BehaviorTree::restart`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(EntityEvent, Message, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TreeRestarted {
+    pub entity: Entity,
+}
+
+/// Triggers the event for observers, then writes it as message.
+pub(crate) fn notify<E>(world: &mut World, event: E)
+where
+    E: Message + Clone,
+    for<'a> E: Event<Trigger<'a>: Default>,
+{
+    world.trigger(event.clone());
+    world.write_message(event);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tester_util::prelude::*;
+
+    #[derive(Resource, Default, Debug, PartialEq, Eq)]
+    struct EventLog(Vec<&'static str>);
+
+    #[test]
+    fn test_observe_started_and_completed() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()))
+            .init_resource::<EventLog>()
+            .add_observer(|_: On<TreeStarted>, mut log: ResMut<EventLog>| {
+                log.0.push("started");
+            })
+            .add_observer(|event: On<TreeCompleted>, mut log: ResMut<EventLog>| {
+                assert!(event.result == NodeResult::Success);
+                log.0.push("completed");
+            });
+        let task = TesterTask0::new(1, NodeResult::Success);
+        let tree = BehaviorTree::from_node(
+            task,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let _entity = app.world_mut().spawn(tree).id();
+        app.update();
+        app.update(); // 0, complete
+        app.update(); // nop
+        let found = app.world().resource::<EventLog>();
+        assert!(
+            found == &EventLog(vec!["started", "completed"]),
+            "Observers should receive the lifecycle events. found: {:?}",
+            found
+        );
+    }
+
+    #[test]
+    fn test_read_aborted_and_restarted() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let task = TesterTask0::new(3, NodeResult::Success);
+        let tree = BehaviorTree::from_node(
+            task,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        app.update();
+        BehaviorTree::restart(app.world_mut(), entity);
+        app.update();
+        BehaviorTree::abort(app.world_mut(), entity);
+        let restarted = app.world().resource::<Messages<TreeRestarted>>();
+        let found: Vec<_> = restarted.get_cursor().read(restarted).copied().collect();
+        assert!(
+            found == vec![TreeRestarted { entity }],
+            "Restart should be written as message. found: {:?}",
+            found
+        );
+        let aborted = app.world().resource::<Messages<TreeAborted>>();
+        let found: Vec<_> = aborted.get_cursor().read(aborted).copied().collect();
+        assert!(
+            found == vec![TreeAborted { entity }],
+            "Abort should be written as message. found: {:?}",
+            found
+        );
+        let status = app.world().get::<TreeStatus>(entity).unwrap();
+        assert!(
+            status.result() == Some(NodeResult::Failure),
+            "Aborted tree should complete with Failure. found: {:?}",
+            status
+        );
+    }
+}
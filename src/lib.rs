@@ -1,36 +1,52 @@
 //! Behavior tree plugin for Bevy.
 
+use std::{sync::Arc, time::Duration};
+
 use bevy::{
     ecs::{intern::Interned, schedule::ScheduleLabel},
     prelude::*,
 };
 
+pub mod blackboard;
+pub mod compiled;
 pub mod conditional;
 pub mod converter;
+pub mod events;
+#[cfg(feature = "serde")]
+pub mod loader;
 pub mod node;
 pub mod parallel;
+pub mod reload;
 pub mod sequential;
+pub mod subtree;
 pub mod task;
+pub mod timer;
 
 #[cfg(test)]
 mod tester_util;
 
-use node::{Node, NodeStatus};
+use events::{TreeAborted, TreeCompleted, TreeRestarted, TreeStarted, notify};
+use node::{Node, NodeResult, NodeStatus, VacantState};
+use reload::{ReloadPolicy, TreeRoots};
 
 /// Module for convenient imports. Use with `use bevior_tree::prelude::*;`.
 pub mod prelude {
-    #[cfg(feature = "serde")]
-    pub use crate::BehaviorTreeSource;
     pub use crate::{
         BehaviorTree, BehaviorTreePlugin, BehaviorTreeRoot, BehaviorTreeSystemSet, Freeze,
-        TreeStatus, conditional::prelude::*, converter::prelude::*, node::prelude::*,
-        parallel::prelude::*, sequential::prelude::*, task::prelude::*,
+        RestartPolicy, RestartTracker, TreeStatus, blackboard::prelude::*, compiled::prelude::*,
+        conditional::prelude::*, converter::prelude::*, events::prelude::*, node::prelude::*,
+        parallel::prelude::*, reload::prelude::*, sequential::prelude::*, subtree::prelude::*,
+        task::prelude::*, timer::prelude::*,
     };
+    #[cfg(feature = "serde")]
+    pub use crate::{BehaviorTreeSource, TreeLoadError, loader::prelude::*};
 }
 
 /// Add to your app to use this crate.
 pub struct BehaviorTreePlugin {
     schedule: Interned<dyn ScheduleLabel>,
+    reload_policy: ReloadPolicy,
+    compile: bool,
 }
 impl BehaviorTreePlugin {
     /// Adds the systems to the given schedule rather than default [`PostUpdate`].
@@ -38,24 +54,61 @@ impl BehaviorTreePlugin {
         self.schedule = schedule.intern();
         self
     }
+    /// Sets what to do with the running trees when their root asset is modified.
+    pub fn with_reload_policy(mut self, reload_policy: ReloadPolicy) -> Self {
+        self.reload_policy = reload_policy;
+        self
+    }
+    /// Wraps the roots loaded from the files in [`compiled::CompiledTree`].
+    /// Trees made by [`BehaviorTree::from_node`] are left as they are.
+    pub fn with_compile(mut self, compile: bool) -> Self {
+        self.compile = compile;
+        self
+    }
 }
 impl Default for BehaviorTreePlugin {
     fn default() -> Self {
         Self {
             schedule: PostUpdate.intern(),
+            reload_policy: ReloadPolicy::default(),
+            compile: false,
         }
     }
 }
 impl Plugin for BehaviorTreePlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<Assets<BehaviorTreeRoot>>().add_systems(
-            self.schedule,
-            (update).in_set(BehaviorTreeSystemSet::Update),
-        );
+        app.init_resource::<Assets<BehaviorTreeRoot>>()
+            .init_resource::<TreeRoots>()
+            .init_resource::<timer::UpdateCount>()
+            .init_resource::<task::FinishedTasks>()
+            .insert_resource(self.reload_policy)
+            .add_systems(
+                self.schedule,
+                (
+                    reload::reload_modified_roots,
+                    timer::count_updates,
+                    task::read_task_finished,
+                    update,
+                )
+                    .chain()
+                    .in_set(BehaviorTreeSystemSet::Update),
+            )
+            .add_message::<TreeStarted>()
+            .add_message::<TreeCompleted>()
+            .add_message::<TreeAborted>()
+            .add_message::<TreeRestarted>()
+            .add_message::<task::TaskFinished>()
+            .add_observer(abort_on_replace)
+            .add_observer(task::observe_task_finished);
         #[cfg(feature = "serde")]
         {
             app.init_asset::<BehaviorTreeRoot>()
-                .add_systems(PreUpdate, load_from_source);
+                .register_asset_loader(loader::BehaviorTreeLoader::new(self.compile))
+                .configure_sets(
+                    self.schedule,
+                    BehaviorTreeSystemSet::Update.after(bevy::asset::AssetEventSystems),
+                )
+                .add_systems(PreUpdate, (load_from_source, report_load_errors));
         }
     }
 }
@@ -71,7 +124,7 @@ pub enum BehaviorTreeSystemSet {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Asset, TypePath, Debug)]
 pub struct BehaviorTreeRoot {
-    node: Box<dyn Node>,
+    node: Arc<dyn Node>,
 }
 
 /// Component to specify the source path of the behavior tree asset.
@@ -84,7 +137,7 @@ pub struct BehaviorTreeSource {
 /// Behavior tree component.
 /// Nodes of the tree receive the entity with this component.
 #[derive(Component, Clone, Debug)]
-#[require(TreeStatus)]
+#[require(TreeStatus, blackboard::Blackboard)]
 pub struct BehaviorTree {
     root: Handle<BehaviorTreeRoot>,
 }
@@ -94,17 +147,15 @@ impl BehaviorTree {
     }
     pub fn from_node<N: Node>(node: N, asset_server: &mut Assets<BehaviorTreeRoot>) -> Self {
         let handle = asset_server.add(BehaviorTreeRoot {
-            node: Box::new(node),
+            node: Arc::new(node),
         });
         Self { root: handle }
     }
     pub fn begin(&self, world: &mut World, entity: Entity) -> NodeStatus {
-        world.resource_scope(|world, assets: Mut<Assets<BehaviorTreeRoot>>| {
-            assets
-                .get(&self.root)
-                .map(|root| root.node.as_ref().begin(world, entity))
-                .unwrap_or(NodeStatus::Beginning)
-        })
+        match TreeRoots::get(world, self.root.id()) {
+            None => NodeStatus::Beginning,
+            Some(root) => root.begin(world, entity),
+        }
     }
     pub fn resume(
         &self,
@@ -112,15 +163,131 @@ impl BehaviorTree {
         entity: Entity,
         state: Box<dyn node::NodeState>,
     ) -> NodeStatus {
-        world.resource_scope(|world, assets: Mut<Assets<BehaviorTreeRoot>>| {
-            match assets.get(&self.root) {
-                None => NodeStatus::Pending(state),
-                Some(root) => root.node.as_ref().resume(world, entity, state),
+        match TreeRoots::get(world, self.root.id()) {
+            None => NodeStatus::Pending(state),
+            Some(root) => root.resume(world, entity, state),
+        }
+    }
+    /// Resumes the tree, updating the state in place.
+    /// Returns `None` while pending, including while the root is not loaded.
+    pub fn resume_in_place(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        state: &mut Box<dyn node::NodeState>,
+    ) -> Option<NodeResult> {
+        TreeRoots::get(world, self.root.id())
+            .and_then(|root| root.resume_in_place(world, entity, state))
+    }
+    pub fn force_exit(&self, world: &mut World, entity: Entity, state: Box<dyn node::NodeState>) {
+        if let Some(root) = TreeRoots::get(world, self.root.id()) {
+            root.force_exit(world, entity, state);
+        }
+    }
+
+    /// Force exits the running nodes of the tree on the entity, then begins it again on the next update.
+    pub fn restart(world: &mut World, entity: Entity) {
+        if Self::exit_with(world, entity, NodeStatus::Beginning).is_some() {
+            notify(world, TreeRestarted { entity });
+        }
+    }
+
+    /// Force exits the running nodes of the tree on the entity, then completes it with Failure.
+    /// [`RestartPolicy`] applies as usual, insert [`Freeze`] too if you want to keep it stopped.
+    /// Does nothing unless the tree is running.
+    pub fn abort(world: &mut World, entity: Entity) {
+        if Self::exit_with(world, entity, NodeStatus::Complete(NodeResult::Failure)) == Some(true) {
+            record_completion(world, entity);
+            notify(world, TreeAborted { entity });
+        }
+    }
+
+    /// Replaces the status of the tree, force exiting the pending state if any.
+    /// Aborting is only meaningful for running trees, so keeps the status of the others.
+    /// Returns whether the tree was running, or None if the entity does not have the tree.
+    fn exit_with(world: &mut World, entity: Entity, status: NodeStatus) -> Option<bool> {
+        let tree = world.get::<BehaviorTree>(entity).cloned()?;
+        let mut tree_status = world.get_mut::<TreeStatus>(entity)?;
+        let aborting = matches!(status, NodeStatus::Complete(_));
+        if aborting && !matches!(tree_status.0, NodeStatus::Pending(_)) {
+            return Some(false);
+        }
+        match std::mem::replace(&mut tree_status.0, status) {
+            // The update resuming the tree force exits it.
+            NodeStatus::Pending(state) if VacantState::is(state.as_ref()) => Some(true),
+            NodeStatus::Pending(state) => {
+                tree.force_exit(world, entity, state);
+                Some(true)
             }
-        })
+            _ => Some(false),
+        }
     }
 }
 
+/// Add to the same entity with the BehaviorTree to begin the tree again after it completed.
+/// Without this component, completed trees stay completed.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+#[require(RestartTracker)]
+pub enum RestartPolicy {
+    #[default]
+    Never,
+    Always,
+    OnSuccess,
+    OnFailure,
+    /// Restart when the duration passed since the completion.
+    AfterDelay(Duration),
+    /// Restart until the tree completes the given times in total.
+    MaxRuns(u32),
+}
+impl RestartPolicy {
+    fn should_restart(&self, result: NodeResult, tracker: &RestartTracker, now: Duration) -> bool {
+        match self {
+            RestartPolicy::Never => false,
+            RestartPolicy::Always => true,
+            RestartPolicy::OnSuccess => result == NodeResult::Success,
+            RestartPolicy::OnFailure => result == NodeResult::Failure,
+            RestartPolicy::AfterDelay(delay) => tracker
+                .completed_at
+                .is_none_or(|completed_at| now.saturating_sub(completed_at) >= *delay),
+            RestartPolicy::MaxRuns(max_runs) => tracker.runs < *max_runs,
+        }
+    }
+}
+
+/// Records completions of the tree for [`RestartPolicy`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct RestartTracker {
+    runs: u32,
+    completed_at: Option<Duration>,
+}
+impl RestartTracker {
+    /// Number of times the tree completed.
+    pub fn runs(&self) -> u32 {
+        self.runs
+    }
+    /// Elapsed time of [`Time`] when the tree completed last.
+    pub fn completed_at(&self) -> Option<Duration> {
+        self.completed_at
+    }
+}
+
+fn record_completion(world: &mut World, entity: Entity) {
+    let now = elapsed(world);
+    if let Some(mut tracker) = world.get_mut::<RestartTracker>(entity) {
+        tracker.runs += 1;
+        tracker.completed_at = Some(now);
+    }
+}
+
+fn elapsed(world: &World) -> Duration {
+    world
+        .get_resource::<Time>()
+        .map(|time| time.elapsed())
+        .unwrap_or_default()
+}
+
 /// Add to the same entity with the BehaviorTree to temporarily freeze the update.
 /// You may prefer [`conditional::ElseFreeze`] node.
 /// Freezes transition of the tree, not running task.
@@ -129,9 +296,27 @@ impl BehaviorTree {
 pub struct Freeze;
 
 /// Represents the state of the tree.
+/// Updated in place, and marked as changed only when the tree begins or completes,
+///  not on each update of the running tree.
+/// While the tree is being updated, it stays running with a placeholder of the state.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Component, Debug)]
 pub struct TreeStatus(NodeStatus);
+impl TreeStatus {
+    pub fn status(&self) -> &NodeStatus {
+        &self.0
+    }
+    /// Result of the tree if completed.
+    pub fn result(&self) -> Option<NodeResult> {
+        self.0.result()
+    }
+    pub fn is_running(&self) -> bool {
+        matches!(self.0, NodeStatus::Pending(_))
+    }
+    pub fn is_complete(&self) -> bool {
+        matches!(self.0, NodeStatus::Complete(_))
+    }
+}
 
 impl Default for TreeStatus {
     fn default() -> Self {
@@ -140,45 +325,269 @@ impl Default for TreeStatus {
 }
 
 /// The system to update the states of the behavior trees attached to entities.
+#[allow(clippy::type_complexity)]
 fn update(
     world: &mut World,
-    query: &mut QueryState<(Entity, &BehaviorTree), (With<TreeStatus>, Without<Freeze>)>,
+    query: &mut QueryState<
+        (
+            Entity,
+            &BehaviorTree,
+            Option<(&RestartPolicy, &RestartTracker)>,
+        ),
+        (With<TreeStatus>, Without<Freeze>),
+    >,
 ) {
+    let now = elapsed(world);
     let targets = query
         .iter(world)
-        .map(|(entity, tree)| (entity, tree.clone()))
+        .map(|(entity, tree, restart)| {
+            let restart = restart.map(|(policy, tracker)| (*policy, *tracker));
+            (entity, tree.clone(), restart)
+        })
         .collect::<Vec<_>>();
-    targets.into_iter().for_each(|(entity, tree)| {
-        if let Some(TreeStatus(status)) = world.entity_mut(entity).take::<TreeStatus>() {
-            let new_status = match status {
-                NodeStatus::Beginning => tree.begin(world, entity),
-                NodeStatus::Pending(state) => tree.resume(world, entity, state),
-                NodeStatus::Complete(_) => status,
-            };
-            world.entity_mut(entity).insert(TreeStatus(new_status));
+    targets.into_iter().for_each(|(entity, tree, restart)| {
+        // Takes the state out of the component rather than removing it,
+        //  so that the entity stays in its archetype and the tree stays visible as running.
+        let Some(status) = world.get_mut::<TreeStatus>(entity).map(|mut tree_status| {
+            match &mut tree_status.bypass_change_detection().0 {
+                NodeStatus::Pending(state) => {
+                    NodeStatus::Pending(std::mem::replace(state, Box::new(VacantState)))
+                }
+                NodeStatus::Beginning => NodeStatus::Beginning,
+                NodeStatus::Complete(result) => NodeStatus::Complete(*result),
+            }
+        }) else {
+            return;
+        };
+        let was_pending = matches!(status, NodeStatus::Pending(_));
+        let was_beginning = matches!(status, NodeStatus::Beginning);
+        let new_status = match status {
+            NodeStatus::Beginning => begin(world, entity, &tree),
+            NodeStatus::Pending(mut state) => {
+                let result = tree.resume_in_place(world, entity, &mut state);
+                NodeStatus::from_resumed(result, state)
+            }
+            NodeStatus::Complete(result)
+                if restart.is_some_and(|(policy, tracker)| {
+                    policy.should_restart(result, &tracker, now)
+                }) =>
+            {
+                notify(world, TreeRestarted { entity });
+                begin(world, entity, &tree)
+            }
+            NodeStatus::Complete(_) => return,
+        };
+        // Exited by abort, restart, replace or despawn while resuming, which notified it.
+        let exited = was_pending
+            && world.get_entity(entity).map_or(true, |entity_ref| {
+                entity_ref
+                    .get::<TreeStatus>()
+                    .is_some_and(|tree_status| !is_resuming(tree_status))
+            });
+        let same_tree = world
+            .get::<BehaviorTree>(entity)
+            .is_some_and(|current| current.root == tree.root);
+        if exited || !same_tree {
+            if let Some(mut tree_status) = world.get_mut::<TreeStatus>(entity)
+                && is_resuming(&tree_status)
+            {
+                tree_status.0 = NodeStatus::Beginning;
+            }
+            if let NodeStatus::Pending(state) = new_status {
+                tree.force_exit(world, entity, state);
+                if !exited {
+                    // The tree was removed, replaced or despawned while beginning.
+                    notify(world, TreeAborted { entity });
+                }
+            }
+            return;
+        }
+        let result = new_status.result();
+        let unchanged = match new_status {
+            NodeStatus::Pending(_) => was_pending,
+            NodeStatus::Beginning => was_beginning,
+            NodeStatus::Complete(_) => false,
+        };
+        match world.get_mut::<TreeStatus>(entity) {
+            Some(mut tree_status) if unchanged => {
+                tree_status.bypass_change_detection().0 = new_status;
+            }
+            Some(mut tree_status) => tree_status.0 = new_status,
+            None => {
+                world.entity_mut(entity).insert(TreeStatus(new_status));
+            }
+        }
+        if let Some(result) = result {
+            record_completion(world, entity);
+            notify(world, TreeCompleted { entity, result });
         }
     });
 }
 
+/// Whether the tree is being resumed by [`update`], with the placeholder of the state.
+fn is_resuming(tree_status: &TreeStatus) -> bool {
+    matches!(&tree_status.0, NodeStatus::Pending(state) if VacantState::is(state.as_ref()))
+}
+
+/// Begins the tree, notifying if the root is available.
+fn begin(world: &mut World, entity: Entity, tree: &BehaviorTree) -> NodeStatus {
+    let status = tree.begin(world, entity);
+    if !matches!(status, NodeStatus::Beginning) {
+        notify(world, TreeStarted { entity });
+    }
+    status
+}
+
+/// Observer to force exit the running tree when [`BehaviorTree`] is removed, replaced or despawned.
+/// The status is reset, so that the replacing tree begins from the start.
+fn abort_on_replace(
+    replace: On<Replace, BehaviorTree>,
+    mut commands: Commands,
+    mut query: Query<(&BehaviorTree, &mut TreeStatus)>,
+) {
+    let entity = replace.entity;
+    let Ok((tree, mut status)) = query.get_mut(entity) else {
+        return;
+    };
+    let resuming = is_resuming(&status);
+    if let NodeStatus::Pending(state) = std::mem::replace(&mut status.0, NodeStatus::Beginning) {
+        let tree = tree.clone();
+        commands.queue(move |world: &mut World| {
+            // The update resuming the tree force exits it.
+            if !resuming {
+                tree.force_exit(world, entity, state);
+            }
+            notify(world, TreeAborted { entity });
+        });
+    }
+}
+
+/// Marks the [`BehaviorTree`] inserted by [`load_from_source`], with the root it was loaded with.
+/// Trees without the marker, or replaced by another root, are left untouched on path changes.
+#[cfg(feature = "serde")]
+#[derive(Component, Debug)]
+pub struct TreeFromSource(AssetId<BehaviorTreeRoot>);
+
+#[cfg(feature = "serde")]
+type SourceQueryData<'a> = (
+    Entity,
+    &'a BehaviorTreeSource,
+    Option<&'a BehaviorTree>,
+    Option<&'a TreeFromSource>,
+);
+
 /// System to load behavior tree assets from source paths.
 /// Attach `BehaviorTreeSource` component to an entity to trigger loading.
+/// Changing the path swaps the tree, force exiting the running one.
+/// A [`BehaviorTree`] inserted explicitly on the entity takes precedence over the source.
 #[cfg(feature = "serde")]
 pub fn load_from_source(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    query: Query<(Entity, &BehaviorTreeSource), (Added<BehaviorTreeSource>, Without<BehaviorTree>)>,
+    query: Query<SourceQueryData, Changed<BehaviorTreeSource>>,
 ) {
-    for (entity, source) in query.iter() {
-        let handle = asset_server.load(&source.path);
-        commands
-            .entity(entity)
-            .insert(BehaviorTree { root: handle });
+    for (entity, source, tree, from_source) in query.iter() {
+        if let Some(tree) = tree
+            && from_source.is_none_or(|from_source| from_source.0 != tree.root.id())
+        {
+            continue;
+        }
+        let handle: Handle<BehaviorTreeRoot> = asset_server.load(&source.path);
+        if tree.is_some_and(|tree| tree.root == handle) {
+            continue;
+        }
+        let mut entity = commands.entity(entity);
+        entity.remove::<TreeLoadError>().insert((
+            TreeFromSource(handle.id()),
+            BehaviorTree {
+                root: handle.clone(),
+            },
+        ));
+        // Failures of handles already alive are not reported again by the asset events.
+        if let bevy::asset::LoadState::Failed(error) = asset_server.load_state(&handle) {
+            entity.insert(TreeLoadError {
+                path: source.path.clone().into(),
+                error: (*error).clone(),
+            });
+        }
+    }
+}
+
+/// Added to the entity with the BehaviorTree when its root asset failed to load.
+/// Removed when the asset is loaded, or the source path is changed.
+#[cfg(feature = "serde")]
+#[derive(Component, Clone, Debug)]
+pub struct TreeLoadError {
+    pub path: bevy::asset::AssetPath<'static>,
+    pub error: bevy::asset::AssetLoadError,
+}
+
+/// System to report failures on loading behavior tree assets as [`TreeLoadError`].
+#[cfg(feature = "serde")]
+pub fn report_load_errors(
+    mut commands: Commands,
+    mut failures: MessageReader<bevy::asset::AssetLoadFailedEvent<BehaviorTreeRoot>>,
+    mut asset_events: MessageReader<AssetEvent<BehaviorTreeRoot>>,
+    trees: Query<(Entity, &BehaviorTree)>,
+) {
+    for failure in failures.read() {
+        for (entity, _) in trees
+            .iter()
+            .filter(|(_, tree)| tree.root.id() == failure.id)
+        {
+            commands.entity(entity).insert(TreeLoadError {
+                path: failure.path.clone(),
+                error: failure.error.clone(),
+            });
+        }
+    }
+    for event in asset_events.read() {
+        let AssetEvent::LoadedWithDependencies { id } = event else {
+            continue;
+        };
+        for (entity, _) in trees.iter().filter(|(_, tree)| tree.root.id() == *id) {
+            commands.entity(entity).try_remove::<TreeLoadError>();
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{node::NodeStatus, tester_util::prelude::*};
+    use std::time::Duration;
+
+    use bevy::time::TimeUpdateStrategy;
+
+    use crate::{
+        node::NodeStatus,
+        task::{TaskChecker, TaskEventListener},
+        tester_util::prelude::*,
+    };
+
+    #[derive(Resource, Default)]
+    struct ExitCount(u32);
+
+    /// Task that never completes, counts exits on the resource.
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[derive(Debug)]
+    struct ExitCountTaskDefinition;
+    #[cfg_attr(feature = "serde", typetag::serde)]
+    impl TaskDefinition for ExitCountTaskDefinition {
+        fn build_checker(&self) -> Box<TaskChecker> {
+            Box::new(IntoSystem::into_system(|In(_): In<Entity>| {
+                TaskStatus::Running
+            }))
+        }
+        fn build_event_listeners(&self) -> Vec<(TaskEvent, Box<TaskEventListener>)> {
+            vec![(
+                TaskEvent::Exit,
+                Box::new(IntoSystem::into_system(
+                    |In(_): In<Entity>, mut count: ResMut<ExitCount>| {
+                        count.0 += 1;
+                    },
+                )),
+            )]
+        }
+    }
 
     #[test]
     fn test_tree_end_with_result() {
@@ -207,6 +616,63 @@ mod tests {
         );
     }
 
+    #[derive(Resource, Default)]
+    struct StatusChanges(Vec<Option<NodeResult>>);
+
+    #[test]
+    fn test_status_changed_on_begin_and_complete() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()))
+            .init_resource::<StatusChanges>()
+            .add_systems(
+                Update,
+                |query: Query<&TreeStatus, Changed<TreeStatus>>,
+                 mut changes: ResMut<StatusChanges>| {
+                    changes.0.extend(query.iter().map(TreeStatus::result));
+                },
+            );
+        let task = TesterTask0::new(3, NodeResult::Success);
+        let tree = BehaviorTree::from_node(
+            task,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        app.world_mut().spawn(tree);
+        for _ in 0..6 {
+            app.update();
+        }
+        // Added, began, then completed.
+        let changes = &app.world().resource::<StatusChanges>().0;
+        assert!(
+            changes == &vec![None, None, Some(NodeResult::Success)],
+            "TreeStatus should be changed only on begin and complete. found: {:?}",
+            changes
+        );
+    }
+
+    #[test]
+    fn test_status_unchanged_while_not_loaded() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()))
+            .init_resource::<StatusChanges>()
+            .add_systems(
+                Update,
+                |query: Query<&TreeStatus, Changed<TreeStatus>>,
+                 mut changes: ResMut<StatusChanges>| {
+                    changes.0.extend(query.iter().map(TreeStatus::result));
+                },
+            );
+        app.world_mut().spawn(BehaviorTree::new(Handle::default()));
+        for _ in 0..5 {
+            app.update();
+        }
+        let changes = &app.world().resource::<StatusChanges>().0;
+        assert!(
+            changes == &vec![None],
+            "TreeStatus should not be changed while the root is not loaded. found: {:?}",
+            changes
+        );
+    }
+
     #[test]
     fn test_freeze() {
         let mut app = App::new();
@@ -256,21 +722,363 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_abort_on_remove() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let task = TesterTask0::new(3, NodeResult::Success);
+        let tree = BehaviorTree::from_node(
+            task,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        app.update();
+        app.update();
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_some(),
+            "Task should be running before removal."
+        );
+        app.world_mut().entity_mut(entity).remove::<BehaviorTree>();
+        app.update();
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_none(),
+            "Running task should exit on removal of the tree."
+        );
+    }
+
+    #[test]
+    fn test_abort_on_replace() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let task = TesterTask0::new(3, NodeResult::Success);
+        let tree = BehaviorTree::from_node(
+            task,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        app.update();
+        app.update();
+        let task = TesterTask1::new(3, NodeResult::Success);
+        let tree = BehaviorTree::from_node(
+            task,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        app.world_mut().entity_mut(entity).insert(tree);
+        app.update();
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_none(),
+            "Running task of the old tree should exit on replace."
+        );
+        assert!(
+            app.world().get::<TesterComponent<1>>(entity).is_some(),
+            "New tree should begin on replace."
+        );
+    }
+
+    #[test]
+    fn test_abort_on_despawn() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()))
+            .init_resource::<ExitCount>();
+        let task = TaskBridge::new(Box::new(ExitCountTaskDefinition));
+        let tree = BehaviorTree::from_node(
+            task,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        app.update();
+        app.update();
+        app.world_mut().despawn(entity);
+        app.update();
+        assert!(
+            app.world().resource::<ExitCount>().0 == 1,
+            "Running task should exit on despawn."
+        );
+    }
+
+    #[test]
+    fn test_restart_always() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let task = TesterTask0::new(1, NodeResult::Success);
+        let tree = BehaviorTree::from_node(
+            task,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let _entity = app.world_mut().spawn((tree, RestartPolicy::Always)).id();
+        app.update();
+        app.update(); // 0, complete
+        app.update(); // restart
+        app.update(); // 0, complete
+        app.update(); // restart
+        app.update(); // 0, complete
+        let found = log_frames(&app);
+        assert!(
+            found == vec![1, 3, 5],
+            "Tree should restart after completion. found: {:?}",
+            found
+        );
+    }
+
+    #[test]
+    fn test_restart_on_failure() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let task = TesterTask0::new(1, NodeResult::Success);
+        let tree = BehaviorTree::from_node(
+            task,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let _entity = app.world_mut().spawn((tree, RestartPolicy::OnFailure)).id();
+        for _ in 0..6 {
+            app.update();
+        }
+        let found = log_frames(&app);
+        assert!(
+            found == vec![1],
+            "Tree should not restart on Success. found: {:?}",
+            found
+        );
+    }
+
+    #[test]
+    fn test_restart_max_runs() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let task = TesterTask0::new(1, NodeResult::Success);
+        let tree = BehaviorTree::from_node(
+            task,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app
+            .world_mut()
+            .spawn((tree, RestartPolicy::MaxRuns(2)))
+            .id();
+        for _ in 0..8 {
+            app.update();
+        }
+        let found = log_frames(&app);
+        assert!(
+            found == vec![1, 3],
+            "Tree should run the given times. found: {:?}",
+            found
+        );
+        let runs = app.world().get::<RestartTracker>(entity).unwrap().runs();
+        assert!(runs == 2, "Tracker should count the runs. found: {}", runs);
+    }
+
+    #[test]
+    fn test_restart_after_delay() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )));
+        let task = TesterTask0::new(1, NodeResult::Success);
+        let tree = BehaviorTree::from_node(
+            task,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let _entity = app
+            .world_mut()
+            .spawn((tree, RestartPolicy::AfterDelay(Duration::from_millis(250))))
+            .id();
+        app.update();
+        app.update(); // 0, complete
+        app.update(); // +100ms
+        app.update(); // +200ms
+        app.update(); // +300ms, restart
+        app.update(); // 0, complete
+        let found = log_frames(&app);
+        assert!(
+            found == vec![1, 5],
+            "Tree should restart after the delay. found: {:?}",
+            found
+        );
+    }
+
+    #[test]
+    fn test_restart_api() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let task = TesterTask0::new(3, NodeResult::Success);
+        let tree = BehaviorTree::from_node(
+            task,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        app.update();
+        app.update(); // 0
+        BehaviorTree::restart(app.world_mut(), entity);
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_none(),
+            "Running task should exit on restart."
+        );
+        app.update(); // restart
+        app.update(); // 0
+        let found = app.world().resource::<TestLog>().log.clone();
+        let expected = vec![
+            TestLogEntry {
+                task_id: 0,
+                updated_count: 0,
+                frame: 1,
+            },
+            TestLogEntry {
+                task_id: 0,
+                updated_count: 0,
+                frame: 3,
+            },
+        ];
+        assert!(
+            found == expected,
+            "Tree should begin again on restart. found: {:?}",
+            found
+        );
+    }
+
+    #[test]
+    fn test_abort_api() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let task = TesterTask0::new(3, NodeResult::Success);
+        let tree = BehaviorTree::from_node(
+            task,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        app.update();
+        app.update(); // 0
+        BehaviorTree::abort(app.world_mut(), entity);
+        app.update(); // nop
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_none(),
+            "Running task should exit on abort."
+        );
+        assert!(
+            matches!(
+                app.world().get::<TreeStatus>(entity),
+                Some(TreeStatus(NodeStatus::Complete(NodeResult::Failure)))
+            ),
+            "Aborted tree should complete with Failure."
+        );
+    }
+
+    #[test]
+    fn test_abort_twice() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let task = TesterTask0::new(3, NodeResult::Success);
+        let tree = BehaviorTree::from_node(
+            task,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app
+            .world_mut()
+            .spawn((tree, RestartPolicy::MaxRuns(2)))
+            .id();
+        BehaviorTree::abort(app.world_mut(), entity); // not began yet
+        app.update();
+        app.update(); // 0
+        BehaviorTree::abort(app.world_mut(), entity);
+        BehaviorTree::abort(app.world_mut(), entity); // already completed
+        let runs = app.world().get::<RestartTracker>(entity).unwrap().runs();
+        let aborted = app.world().resource::<Messages<TreeAborted>>();
+        let found = aborted.get_cursor().read(aborted).count();
+        assert!(
+            runs == 1 && found == 1,
+            "Abort should count only the running tree. runs: {}, aborted: {}",
+            runs,
+            found
+        );
+    }
+
+    #[derive(Resource, Default)]
+    struct RunningOnResume(Vec<bool>);
+
+    /// Node that aborts the tree on resume, records whether the tree was running then.
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[derive(Debug)]
+    struct AbortingNode;
+    #[cfg_attr(feature = "serde", typetag::serde)]
+    impl Node for AbortingNode {
+        fn begin(&self, _world: &mut World, _entity: Entity) -> NodeStatus {
+            NodeStatus::Pending(Box::new(AbortingState))
+        }
+        fn resume(
+            &self,
+            world: &mut World,
+            entity: Entity,
+            state: Box<dyn NodeState>,
+        ) -> NodeStatus {
+            let running = world
+                .get::<TreeStatus>(entity)
+                .is_some_and(TreeStatus::is_running);
+            world.resource_mut::<RunningOnResume>().0.push(running);
+            BehaviorTree::abort(world, entity);
+            NodeStatus::Pending(state)
+        }
+        fn force_exit(&self, world: &mut World, _entity: Entity, _state: Box<dyn NodeState>) {
+            world.resource_mut::<ExitCount>().0 += 1;
+        }
+    }
+
+    /// State for [`AbortingNode`]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[derive(NodeState, Debug)]
+    struct AbortingState;
+
+    #[test]
+    fn test_abort_while_resuming() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()))
+            .init_resource::<ExitCount>()
+            .init_resource::<RunningOnResume>();
+        let tree = BehaviorTree::from_node(
+            AbortingNode,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        for _ in 0..3 {
+            app.update();
+        }
+        let running = &app.world().resource::<RunningOnResume>().0;
+        assert!(
+            running == &vec![true],
+            "Tree should be running while resuming. found: {:?}",
+            running
+        );
+        let aborted = app.world().resource::<Messages<TreeAborted>>();
+        let found = aborted.get_cursor().read(aborted).count();
+        assert!(
+            app.world().resource::<ExitCount>().0 == 1 && found == 1,
+            "Tree aborted while resuming should exit once. aborted: {}",
+            found
+        );
+        assert!(
+            matches!(
+                app.world().get::<TreeStatus>(entity),
+                Some(TreeStatus(NodeStatus::Complete(NodeResult::Failure)))
+            ),
+            "Tree aborted while resuming should complete with Failure."
+        );
+    }
+
     #[cfg(feature = "serde")]
     #[test]
     fn test_save_and_load_roundtrip() {
         use std::fs;
         use std::path::Path;
+        use std::sync::Arc;
 
         let task = TesterTask0::new(1, NodeResult::Success);
         let root = BehaviorTreeRoot {
-            node: Box::new(task),
+            node: Arc::new(task),
         };
 
         // Save the asset to a temporary file
         let test_asset_dir = Path::new("target").join("test_assets");
         fs::create_dir_all(&test_asset_dir).expect("Failed to create test directory");
-        let file_name = "roundtrip_tree.ron";
+        let file_name = "roundtrip_tree.bt.ron";
         let file_path = test_asset_dir.join(file_name);
         let ron = ron::to_string(&root).expect("Failed to serialize test asset");
         fs::write(&file_path, ron).expect("Failed to write test asset");
@@ -320,4 +1128,120 @@ mod tests {
             "BehaviorTree should have result that match with the result of the root."
         );
     }
+
+    #[cfg(feature = "serde")]
+    fn write_test_asset(file_name: &str, node: impl Node) {
+        let root = BehaviorTreeRoot {
+            node: std::sync::Arc::new(node),
+        };
+        let ron = ron::to_string(&root).expect("Failed to serialize test asset");
+        write_test_file(file_name, &ron);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_source_path_change() {
+        write_test_asset(
+            "path_change_0.bt.ron",
+            TesterTask0::new(100, NodeResult::Success),
+        );
+        write_test_asset(
+            "path_change_1.bt.ron",
+            TesterTask1::new(100, NodeResult::Success),
+        );
+
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let source = BehaviorTreeSource {
+            path: "path_change_0.bt.ron".to_string(),
+        };
+        let entity = app.world_mut().spawn(source).id();
+        for _ in 0..50 {
+            app.update();
+        }
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_some(),
+            "Tree from the first path should be running."
+        );
+
+        app.world_mut()
+            .get_mut::<BehaviorTreeSource>(entity)
+            .unwrap()
+            .path = "path_change_1.bt.ron".to_string();
+        for _ in 0..50 {
+            app.update();
+        }
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_none(),
+            "Tree from the old path should exit."
+        );
+        assert!(
+            app.world().get::<TesterComponent<1>>(entity).is_some(),
+            "Tree from the new path should be running."
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_load_error() {
+        let (app, entity) = load_test_file("missing_tree.bt.ron");
+        let error = app.world().get::<TreeLoadError>(entity);
+        assert!(
+            error.is_some_and(|error| error.path.path().ends_with("missing_tree.bt.ron")),
+            "Load failure should be reported. found: {:?}",
+            error
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_explicit_tree_kept() {
+        write_test_asset(
+            "explicit_tree.bt.ron",
+            TesterTask1::new(100, NodeResult::Success),
+        );
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let source = BehaviorTreeSource {
+            path: "explicit_tree.bt.ron".to_string(),
+        };
+        let tree = BehaviorTree::from_node(
+            TesterTask0::new(100, NodeResult::Success),
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn((source, tree)).id();
+        for _ in 0..50 {
+            app.update();
+        }
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_some(),
+            "Explicitly inserted tree should be running."
+        );
+        assert!(
+            app.world().get::<TesterComponent<1>>(entity).is_none(),
+            "Tree from the source should not replace the explicit one."
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_load_error_alive_handle() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let source = BehaviorTreeSource {
+            path: "missing_alive.bt.ron".to_string(),
+        };
+        app.world_mut().spawn(source.clone());
+        for _ in 0..50 {
+            app.update();
+        }
+        let entity = app.world_mut().spawn(source).id();
+        for _ in 0..5 {
+            app.update();
+        }
+        assert!(
+            app.world().get::<TreeLoadError>(entity).is_some(),
+            "Failure of the handle already alive should be reported."
+        );
+    }
 }
@@ -0,0 +1,579 @@
+//! Asset loader for behavior trees written in RON, with `.bt.ron` extension.
+
+use std::{cell::RefCell, fmt, marker::PhantomData, sync::Arc};
+
+use bevy::{
+    asset::{AssetLoader, AssetPath, Handle, LoadContext, io::Reader},
+    platform::collections::{HashMap, HashSet},
+    reflect::TypePath,
+};
+use serde::de::{
+    self, DeserializeSeed, Deserializer, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor,
+};
+
+use crate::{BehaviorTreeRoot, compiled::CompiledTree, node::Node};
+
+pub mod prelude {
+    pub use super::{BehaviorTreeLoader, BehaviorTreeLoaderError};
+}
+
+/// Loads [`BehaviorTreeRoot`] from RON files.
+/// Enabled by [`crate::BehaviorTreePlugin`].
+///
+/// Roots referenced by [`crate::subtree::SubTree`] are loaded as dependencies.
+#[derive(Default, TypePath)]
+pub struct BehaviorTreeLoader {
+    compile: bool,
+}
+impl BehaviorTreeLoader {
+    /// Wraps the loaded roots in [`CompiledTree`] if `compile`.
+    pub fn new(compile: bool) -> Self {
+        Self { compile }
+    }
+}
+
+impl AssetLoader for BehaviorTreeLoader {
+    type Asset = BehaviorTreeRoot;
+    type Settings = ();
+    type Error = BehaviorTreeLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let includes = collect_includes(&bytes)?;
+        check_cycles(load_context, includes.clone()).await?;
+        let handles = includes
+            .into_iter()
+            .map(|path| (path.clone(), load_context.load(path)))
+            .collect();
+        let node = deserialize(&bytes, SubTreePaths::Resolve(handles)).0?.node;
+        let node = if self.compile {
+            Box::new(CompiledTree::from_boxed(node))
+        } else {
+            node
+        };
+        Ok(BehaviorTreeRoot {
+            node: Arc::from(node),
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["bt.ron"]
+    }
+}
+
+/// Failure on loading behavior tree from RON.
+#[derive(Debug)]
+pub enum BehaviorTreeLoaderError {
+    Io(std::io::Error),
+    /// The file is not a valid tree.
+    /// `type_name` is the typetag name of the innermost node or builder enclosing the failure, if any.
+    Parse {
+        line: usize,
+        column: usize,
+        type_name: Option<String>,
+        message: String,
+    },
+    /// The tree includes itself through [`crate::subtree::SubTree`].
+    Cycle(Vec<AssetPath<'static>>),
+}
+impl BehaviorTreeLoaderError {
+    fn parse(error: ron::error::SpannedError, type_name: Option<String>) -> Self {
+        let position = error.span.start;
+        Self::Parse {
+            line: position.line,
+            column: position.col,
+            type_name,
+            message: error.code.to_string(),
+        }
+    }
+}
+impl fmt::Display for BehaviorTreeLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Failed to read behavior tree: {}", error),
+            Self::Parse {
+                line,
+                column,
+                type_name: Some(type_name),
+                message,
+            } => write!(f, "{}:{}: in `{}`: {}", line, column, type_name, message),
+            Self::Parse {
+                line,
+                column,
+                type_name: None,
+                message,
+            } => write!(f, "{}:{}: {}", line, column, message),
+            Self::Cycle(paths) => {
+                let paths: Vec<_> = paths.iter().map(ToString::to_string).collect();
+                write!(f, "SubTree includes cycle: {}", paths.join(" -> "))
+            }
+        }
+    }
+}
+impl std::error::Error for BehaviorTreeLoaderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Parse { .. } | Self::Cycle(_) => None,
+        }
+    }
+}
+impl From<std::io::Error> for BehaviorTreeLoaderError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// How [`crate::subtree::SubTree`] being deserialized gets the handle from the path.
+enum SubTreePaths {
+    /// Records the paths, with placeholder handles.
+    Collect(Vec<AssetPath<'static>>),
+    Resolve(HashMap<AssetPath<'static>, Handle<BehaviorTreeRoot>>),
+}
+
+thread_local! {
+    static SUBTREE_PATHS: RefCell<Option<SubTreePaths>> = const { RefCell::new(None) };
+}
+
+/// Handle for [`crate::subtree::SubTree`] being deserialized, None if not in the loader.
+pub(crate) fn subtree_handle(path: AssetPath<'static>) -> Option<Handle<BehaviorTreeRoot>> {
+    SUBTREE_PATHS.with_borrow_mut(|paths| match paths.as_mut()? {
+        SubTreePaths::Collect(found) => {
+            found.push(path);
+            Some(Handle::default())
+        }
+        SubTreePaths::Resolve(handles) => handles.get(&path).cloned(),
+    })
+}
+
+/// Same as [`BehaviorTreeRoot`], holding the node to compile.
+#[derive(serde::Deserialize)]
+#[serde(rename = "BehaviorTreeRoot")]
+struct LoadedRoot {
+    node: Box<dyn Node>,
+}
+
+fn deserialize(
+    bytes: &[u8],
+    paths: SubTreePaths,
+) -> (Result<LoadedRoot, BehaviorTreeLoaderError>, SubTreePaths) {
+    let outer = SUBTREE_PATHS.replace(Some(paths));
+    let outer_tags = TYPE_TAGS.take();
+    let result = ron::Options::default().from_bytes_seed(bytes, Tracked(PhantomData));
+    let failed = TYPE_TAGS.replace(outer_tags).failed;
+    let paths = SUBTREE_PATHS
+        .replace(outer)
+        .expect("SubTree paths should be kept while deserializing.");
+    (
+        result.map_err(|error| BehaviorTreeLoaderError::parse(error, failed)),
+        paths,
+    )
+}
+
+/// Typetag names of the maps being deserialized, to tell which node or builder failed.
+#[derive(Default)]
+struct TypeTags {
+    /// Names of the maps from the outermost, None for the maps without `"type"` entry.
+    stack: Vec<Option<String>>,
+    /// Last string deserialized, to find the `"type"` key and its value.
+    last_str: Option<String>,
+    /// Innermost name enclosing the failure.
+    failed: Option<String>,
+}
+
+thread_local! {
+    static TYPE_TAGS: RefCell<TypeTags> = RefCell::new(TypeTags::default());
+}
+
+/// Deserializer, seed and accessors forwarding to the inner ones, recording [`TypeTags`] on the way.
+struct Tracked<T>(T);
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for Tracked<S> {
+    type Value = S::Value;
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.0.deserialize(Tracked(deserializer))
+    }
+}
+
+macro_rules! forward_deserialize {
+    ($($method:ident($($arg:ident: $ty:ty),*)),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, D::Error> {
+                self.0.$method($($arg,)* Tracked(visitor))
+            }
+        )*
+    };
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for Tracked<D> {
+    type Error = D::Error;
+    forward_deserialize!(
+        deserialize_any(),
+        deserialize_bool(),
+        deserialize_i8(),
+        deserialize_i16(),
+        deserialize_i32(),
+        deserialize_i64(),
+        deserialize_i128(),
+        deserialize_u8(),
+        deserialize_u16(),
+        deserialize_u32(),
+        deserialize_u64(),
+        deserialize_u128(),
+        deserialize_f32(),
+        deserialize_f64(),
+        deserialize_char(),
+        deserialize_str(),
+        deserialize_string(),
+        deserialize_bytes(),
+        deserialize_byte_buf(),
+        deserialize_option(),
+        deserialize_unit(),
+        deserialize_unit_struct(name: &'static str),
+        deserialize_newtype_struct(name: &'static str),
+        deserialize_seq(),
+        deserialize_tuple(len: usize),
+        deserialize_tuple_struct(name: &'static str, len: usize),
+        deserialize_map(),
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]),
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]),
+        deserialize_identifier(),
+        deserialize_ignored_any(),
+    );
+    fn is_human_readable(&self) -> bool {
+        self.0.is_human_readable()
+    }
+}
+
+macro_rules! forward_visit {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method<E: de::Error>(self, value: $ty) -> Result<Self::Value, E> {
+                self.0.$method(value)
+            }
+        )*
+    };
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for Tracked<V> {
+    type Value = V::Value;
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.0.expecting(formatter)
+    }
+    forward_visit!(
+        visit_bool(bool),
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f32(f32),
+        visit_f64(f64),
+        visit_char(char),
+        visit_bytes(&[u8]),
+        visit_borrowed_bytes(&'de [u8]),
+        visit_byte_buf(Vec<u8>),
+    );
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        TYPE_TAGS.with_borrow_mut(|tags| tags.last_str = Some(value.to_string()));
+        self.0.visit_str(value)
+    }
+    fn visit_borrowed_str<E: de::Error>(self, value: &'de str) -> Result<Self::Value, E> {
+        TYPE_TAGS.with_borrow_mut(|tags| tags.last_str = Some(value.to_string()));
+        self.0.visit_borrowed_str(value)
+    }
+    fn visit_string<E: de::Error>(self, value: String) -> Result<Self::Value, E> {
+        TYPE_TAGS.with_borrow_mut(|tags| tags.last_str = Some(value.clone()));
+        self.0.visit_string(value)
+    }
+    fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.0.visit_none()
+    }
+    fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+        self.0.visit_unit()
+    }
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.0.visit_some(Tracked(deserializer))
+    }
+    fn visit_newtype_struct<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        self.0.visit_newtype_struct(Tracked(deserializer))
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        self.0.visit_seq(Tracked(seq))
+    }
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        TYPE_TAGS.with_borrow_mut(|tags| tags.stack.push(None));
+        let result = self.0.visit_map(TrackedMap {
+            map,
+            tag_next: false,
+        });
+        TYPE_TAGS.with_borrow_mut(|tags| {
+            if result.is_err() && tags.failed.is_none() {
+                tags.failed = tags.stack.iter().rev().flatten().next().cloned();
+            }
+            tags.stack.pop();
+        });
+        result
+    }
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        self.0.visit_enum(Tracked(data))
+    }
+}
+
+impl<'de, A: SeqAccess<'de>> SeqAccess<'de> for Tracked<A> {
+    type Error = A::Error;
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, A::Error> {
+        self.0.next_element_seed(Tracked(seed))
+    }
+    fn size_hint(&self) -> Option<usize> {
+        self.0.size_hint()
+    }
+}
+
+/// Map recording the value of the `"type"` key as the name of the map.
+struct TrackedMap<A> {
+    map: A,
+    tag_next: bool,
+}
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for TrackedMap<A> {
+    type Error = A::Error;
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, A::Error> {
+        TYPE_TAGS.with_borrow_mut(|tags| tags.last_str = None);
+        let key = self.map.next_key_seed(Tracked(seed))?;
+        self.tag_next = TYPE_TAGS.with_borrow(|tags| tags.last_str.as_deref() == Some("type"));
+        Ok(key)
+    }
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, A::Error> {
+        TYPE_TAGS.with_borrow_mut(|tags| tags.last_str = None);
+        let value = self.map.next_value_seed(Tracked(seed));
+        if std::mem::take(&mut self.tag_next) {
+            TYPE_TAGS.with_borrow_mut(|tags| {
+                let name = tags.last_str.take();
+                if let Some(top) = tags.stack.last_mut() {
+                    *top = name;
+                }
+            });
+        }
+        value
+    }
+    fn size_hint(&self) -> Option<usize> {
+        self.map.size_hint()
+    }
+}
+
+impl<'de, A: EnumAccess<'de>> EnumAccess<'de> for Tracked<A> {
+    type Error = A::Error;
+    type Variant = Tracked<A::Variant>;
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), A::Error> {
+        let (value, variant) = self.0.variant_seed(Tracked(seed))?;
+        Ok((value, Tracked(variant)))
+    }
+}
+
+impl<'de, A: VariantAccess<'de>> VariantAccess<'de> for Tracked<A> {
+    type Error = A::Error;
+    fn unit_variant(self) -> Result<(), A::Error> {
+        self.0.unit_variant()
+    }
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, A::Error> {
+        self.0.newtype_variant_seed(Tracked(seed))
+    }
+    fn tuple_variant<V: Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value, A::Error> {
+        self.0.tuple_variant(len, Tracked(visitor))
+    }
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, A::Error> {
+        self.0.struct_variant(fields, Tracked(visitor))
+    }
+}
+
+/// Paths of the roots that the tree includes directly.
+fn collect_includes(bytes: &[u8]) -> Result<Vec<AssetPath<'static>>, BehaviorTreeLoaderError> {
+    let (result, paths) = deserialize(bytes, SubTreePaths::Collect(Vec::new()));
+    result?;
+    let SubTreePaths::Collect(paths) = paths else {
+        unreachable!("Collected paths should be returned.");
+    };
+    Ok(paths)
+}
+
+/// Follows the includes depth first, to find the path back to the tree being loaded.
+/// Trees failing to read or parse are skipped, their own loads report it.
+async fn check_cycles(
+    load_context: &mut LoadContext<'_>,
+    includes: Vec<AssetPath<'static>>,
+) -> Result<(), BehaviorTreeLoaderError> {
+    let mut chain = vec![(load_context.path().clone(), includes.into_iter())];
+    let mut visited = HashSet::new();
+    while let Some((_, children)) = chain.last_mut() {
+        let Some(child) = children.next() else {
+            chain.pop();
+            continue;
+        };
+        if chain.iter().any(|(path, _)| *path == child) {
+            let mut paths: Vec<_> = chain.into_iter().map(|(path, _)| path).collect();
+            paths.push(child);
+            return Err(BehaviorTreeLoaderError::Cycle(paths));
+        }
+        if !visited.insert(child.clone()) {
+            continue;
+        }
+        let Ok(bytes) = load_context.read_asset_bytes(&child).await else {
+            continue;
+        };
+        let Ok(grandchildren) = collect_includes(&bytes) else {
+            continue;
+        };
+        chain.push((child, grandchildren.into_iter()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tester_util::prelude::*;
+
+    fn load_error(file_name: &str, content: &str) -> String {
+        write_test_file(file_name, content);
+        let (app, entity) = load_test_file(file_name);
+        let error = app
+            .world()
+            .get::<TreeLoadError>(entity)
+            .expect("Load failure should be reported.");
+        error.error.to_string()
+    }
+
+    #[test]
+    fn test_load_compiled() {
+        write_test_file(
+            "loader_compiled.bt.ron",
+            r#"(
+    node: {
+        "type": "TesterTask0",
+        "task": (definition: {"type": "TesterTaskDef0", "count": 1, "result": Success}),
+    },
+)"#,
+        );
+        let (app, entity) = load_test_file_with(
+            "loader_compiled.bt.ron",
+            BehaviorTreePlugin::default().with_compile(true),
+        );
+        let tree = app.world().get::<BehaviorTree>(entity).unwrap();
+        let root = app
+            .world()
+            .resource::<Assets<BehaviorTreeRoot>>()
+            .get(&tree.root)
+            .unwrap();
+        let status = app.world().get::<TreeStatus>(entity).unwrap();
+        assert!(
+            format!("{:?}", root.node).starts_with("CompiledTree")
+                && status.result() == Some(NodeResult::Success),
+            "Loaded tree should be compiled and run. found: {:?}, {:?}",
+            root.node,
+            status
+        );
+    }
+
+    #[test]
+    fn test_load_bt_ron() {
+        write_test_file(
+            "loader_tree.bt.ron",
+            r#"(
+    node: {
+        "type": "TesterTask0",
+        "task": (definition: {"type": "TesterTaskDef0", "count": 1, "result": Success}),
+    },
+)"#,
+        );
+        let (app, entity) = load_test_file("loader_tree.bt.ron");
+        let status = app.world().get::<TreeStatus>(entity);
+        assert!(
+            status.is_some_and(|status| status.result() == Some(NodeResult::Success)),
+            "Tree loaded by the loader should run. found: {:?}",
+            status
+        );
+    }
+
+    #[test]
+    fn test_unknown_type_error() {
+        let found = load_error(
+            "loader_unknown.bt.ron",
+            r#"(
+    node: {
+        "type": "NoSuchNode",
+    },
+)"#,
+        );
+        assert!(
+            found.contains("NoSuchNode") && found.contains("2:"),
+            "Error should tell the position and the type name. found: {}",
+            found
+        );
+    }
+
+    #[test]
+    fn test_invalid_field_error() {
+        let found = load_error(
+            "loader_invalid.bt.ron",
+            r#"(
+    node: {
+        "type": "TesterTask0",
+        "task": (definition: {"type": "TesterTaskDef0", "count": "one", "result": Success}),
+    },
+)"#,
+        );
+        assert!(
+            found.contains("TesterTaskDef0") && found.contains("4:"),
+            "Error should tell the position and the type name. found: {}",
+            found
+        );
+    }
+
+    #[test]
+    fn test_error_after_nested_node() {
+        let found = load_error(
+            "loader_nested_invalid.bt.ron",
+            r#"(
+    node: {
+        "type": "ResultConverter",
+        "child": {
+            "type": "TesterTask0",
+            "task": (definition: {"type": "TesterTaskDef0", "count": 1, "result": Success}),
+        },
+        "converter": "{",
+    },
+)"#,
+        );
+        assert!(
+            found.contains("ResultConverter") && found.contains("8:"),
+            "Error should tell the node enclosing the failure. found: {}",
+            found
+        );
+    }
+}
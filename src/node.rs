@@ -3,6 +3,8 @@
 use bevy::prelude::{Entity, World};
 use std::{any::Any, fmt::Debug, ops::Not};
 
+use crate::compiled::FlatNode;
+
 pub mod prelude {
     pub use super::{Node, NodeResult, NodeState, NodeStateError, NodeStatus, WithState};
     pub use derive_nodestate::NodeState;
@@ -13,7 +15,7 @@ pub mod prelude {
 /// State of pending, work in progress nodes.
 /// `#[derive(NodeState)]` is available.
 #[cfg_attr(feature = "serde", typetag::serde(tag = "type"))]
-pub trait NodeState: 'static + Debug + Send + Sync {
+pub trait NodeState: 'static + Any + Debug + Send + Sync {
     fn into_any(self: Box<Self>) -> Box<dyn Any>;
 }
 
@@ -49,17 +51,91 @@ impl NodeStatus {
             _ => None,
         }
     }
+    /// Status from the result of [`Node::resume_in_place`], pending with the state unless completed.
+    pub fn from_resumed(result: Option<NodeResult>, state: Box<dyn NodeState>) -> Self {
+        match result {
+            Some(result) => NodeStatus::Complete(result),
+            None => NodeStatus::Pending(state),
+        }
+    }
+    /// Result for [`Node::resume_in_place`], replacing the state with the pending one unless completed.
+    pub fn into_resumed(self, state: &mut Box<dyn NodeState>) -> Option<NodeResult> {
+        match self {
+            NodeStatus::Pending(next) => {
+                *state = next;
+                None
+            }
+            NodeStatus::Complete(result) => Some(result),
+            NodeStatus::Beginning => panic!("Unexpected NodeStatus::Beginning."),
+        }
+    }
 }
 
 /// Node of behavior trees.
 /// Nodes should not hold the state of execution.
 /// Nodes take state of execution as argument, do things with it, then return the status of the execution.
 /// `#[delegate_node(target)]` is available for shorthand to delegate node.
+///
+/// Composite nodes resume their children with [`Node::resume_in_place`],
+///  which updates the state without boxing it again.
+/// Its default implementation calls [`Node::resume`], so custom nodes work without changes.
+/// To migrate a node, override `resume_in_place` to update the state through [`WithState::downcast_mut`],
+///  then implement `resume` with it and [`NodeStatus::from_resumed`].
 #[cfg_attr(feature = "serde", typetag::serde(tag = "type"))]
-pub trait Node: 'static + Debug + Send + Sync {
+pub trait Node: 'static + IntoBoxedNode + Debug + Send + Sync {
     fn begin(&self, world: &mut World, entity: Entity) -> NodeStatus;
     fn resume(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) -> NodeStatus;
     fn force_exit(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>);
+
+    /// Resumes the node, updating the state in place.
+    /// Returns the result when completed, or `None` while pending.
+    /// The state is not used anymore once completed.
+    fn resume_in_place(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        state: &mut Box<dyn NodeState>,
+    ) -> Option<NodeResult> {
+        self.resume(world, entity, VacantState::take(state))
+            .into_resumed(state)
+    }
+
+    /// Splits the node into the parts of [`crate::compiled::CompiledTree`].
+    /// Nodes are not split by default, and run as leaves of the compiled tree.
+    fn flatten(self: Box<Self>) -> FlatNode {
+        FlatNode::Leaf(self.into_boxed())
+    }
+}
+
+/// Converts into `Box<dyn Node>`, implemented for all the nodes.
+/// Also used by `#[delegate_node(target)]` to flatten the target.
+pub trait IntoBoxedNode {
+    fn into_boxed(self: Box<Self>) -> Box<dyn Node>;
+}
+impl<T: Node> IntoBoxedNode for T {
+    fn into_boxed(self: Box<Self>) -> Box<dyn Node> {
+        self
+    }
+}
+impl IntoBoxedNode for Box<dyn Node> {
+    fn into_boxed(self: Box<Self>) -> Box<dyn Node> {
+        *self
+    }
+}
+
+/// Placeholder of the state taken out to resume.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(derive_nodestate::NodeState, Debug)]
+pub(crate) struct VacantState;
+impl VacantState {
+    /// Takes the state out, leaving the placeholder.
+    /// Box of zero sized type does not allocate.
+    pub(crate) fn take(state: &mut Box<dyn NodeState>) -> Box<dyn NodeState> {
+        std::mem::replace(state, Box::new(Self))
+    }
+    pub(crate) fn is(state: &dyn NodeState) -> bool {
+        (state as &dyn std::any::Any).is::<Self>()
+    }
 }
 
 /// Trait to pair the node and the state.
@@ -74,6 +150,13 @@ pub trait WithState<State: NodeState>: Node {
             Err(_) => Err(NodeStateError::InvalidTypeOfState),
         }
     }
+    /// Borrows the state to update it in place.
+    fn downcast_mut(state: &mut Box<dyn NodeState>) -> Result<&mut State, NodeStateError> {
+        let state: &mut dyn Any = state.as_mut();
+        state
+            .downcast_mut::<State>()
+            .ok_or(NodeStateError::InvalidTypeOfState)
+    }
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
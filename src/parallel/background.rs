@@ -0,0 +1,176 @@
+use bevy::ecs::{entity::Entity, world::World};
+
+use crate::node::prelude::*;
+
+pub mod prelude {
+    pub use super::MainWithBackground;
+}
+
+/// Node that runs the main child with the background children alongside.
+/// Background children are restarted when they complete, at most once in an update.
+/// When the main child completes, force exits the background children and returns the result of the main.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+#[with_state(MainWithBackgroundState)]
+pub struct MainWithBackground {
+    main: Box<dyn Node>,
+    background: Vec<Box<dyn Node>>,
+}
+impl MainWithBackground {
+    pub fn new(main: impl Node, background: Vec<Box<dyn Node>>) -> Self {
+        Self {
+            main: Box::new(main),
+            background,
+        }
+    }
+    fn run_background(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        background_status: &mut [NodeStatus],
+    ) {
+        for (child, status) in self.background.iter().zip(background_status.iter_mut()) {
+            match status {
+                NodeStatus::Pending(child_state) => {
+                    if child.resume_in_place(world, entity, child_state).is_some() {
+                        *status = child.begin(world, entity);
+                    }
+                }
+                _ => *status = child.begin(world, entity),
+            }
+        }
+    }
+    fn exit_background(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        background_status: Vec<NodeStatus>,
+    ) {
+        self.background
+            .iter()
+            .zip(background_status)
+            .for_each(|(child, status)| {
+                if let NodeStatus::Pending(child_state) = status {
+                    child.force_exit(world, entity, child_state);
+                }
+            });
+    }
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl Node for MainWithBackground {
+    fn begin(&self, world: &mut World, entity: Entity) -> NodeStatus {
+        let main_state = match self.main.begin(world, entity) {
+            NodeStatus::Pending(main_state) => main_state,
+            status => return status,
+        };
+        let mut background_status: Vec<_> = self
+            .background
+            .iter()
+            .map(|_| NodeStatus::Beginning)
+            .collect();
+        self.run_background(world, entity, &mut background_status);
+        NodeStatus::Pending(Box::new(MainWithBackgroundState {
+            main_state,
+            background_status,
+        }))
+    }
+
+    fn resume(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        mut state: Box<dyn NodeState>,
+    ) -> NodeStatus {
+        let result = self.resume_in_place(world, entity, &mut state);
+        NodeStatus::from_resumed(result, state)
+    }
+
+    fn resume_in_place(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        state: &mut Box<dyn NodeState>,
+    ) -> Option<NodeResult> {
+        let state = Self::downcast_mut(state).expect("Invalid state.");
+        match self
+            .main
+            .resume_in_place(world, entity, &mut state.main_state)
+        {
+            None => {
+                self.run_background(world, entity, &mut state.background_status);
+                None
+            }
+            Some(result) => {
+                let background_status = std::mem::take(&mut state.background_status);
+                self.exit_background(world, entity, background_status);
+                Some(result)
+            }
+        }
+    }
+
+    fn force_exit(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) {
+        let state = Self::downcast(state).expect("Invalid state.");
+        self.main.force_exit(world, entity, state.main_state);
+        self.exit_background(world, entity, state.background_status);
+    }
+}
+
+/// State for [`MainWithBackground`]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(NodeState, Debug)]
+struct MainWithBackgroundState {
+    main_state: Box<dyn NodeState>,
+    background_status: Vec<NodeStatus>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tester_util::prelude::*;
+
+    #[test]
+    fn test_main_with_background() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let node = MainWithBackground::new(
+            TesterTask0::new(3, NodeResult::Failure),
+            vec![
+                Box::new(TesterTask1::new(1, NodeResult::Success)),
+                Box::new(TesterTask2::new(100, NodeResult::Success)),
+            ],
+        );
+        let tree = BehaviorTree::from_node(
+            node,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        app.update();
+        app.update(); // 0, 1, 2
+        app.update(); // 0, 1 restarted, 2
+        app.update(); // 0, 1 restarted, 2, main completed
+        app.update(); // nop
+        let found: Vec<_> = app
+            .world()
+            .resource::<TestLog>()
+            .log
+            .iter()
+            .filter(|entry| entry.task_id == 1)
+            .map(|entry| (entry.updated_count, entry.frame))
+            .collect();
+        assert!(
+            found == vec![(0, 1), (0, 2), (0, 3)],
+            "Background child should restart on complete until the main completes. found: {:?}",
+            found
+        );
+        let status = app.world().get::<TreeStatus>(entity).unwrap();
+        assert!(
+            status.result() == Some(NodeResult::Failure),
+            "MainWithBackground should return the result of the main. found: {:?}",
+            status
+        );
+        assert!(
+            app.world().get::<TesterComponent<1>>(entity).is_none()
+                && app.world().get::<TesterComponent<2>>(entity).is_none(),
+            "Background children should be force exited."
+        );
+    }
+}
@@ -1,14 +1,18 @@
 //! Composite nodes that run children parallelly.
 
+use std::num::NonZeroUsize;
+
 use bevy::ecs::{entity::Entity, world::World};
 
+use crate::compiled::FlatNode;
 use crate::node::prelude::*;
 use crate::sequential::ResultStrategy;
 
+pub mod background;
 pub mod variants;
 
 pub mod prelude {
-    pub use super::{Parallel, variants::prelude::*};
+    pub use super::{Parallel, background::prelude::*, variants::prelude::*};
 }
 
 /// Composite node that run children parallelly.
@@ -18,6 +22,8 @@ pub mod prelude {
 pub struct Parallel {
     children: Vec<Box<dyn Node>>,
     result_strategy: Box<dyn ResultStrategy>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    limit: Option<NonZeroUsize>,
 }
 impl Parallel {
     /// Creates new [`Parallel`] node.
@@ -29,8 +35,25 @@ impl Parallel {
         Self {
             children,
             result_strategy: Box::new(result_strategy),
+            limit: None,
         }
     }
+    /// Runs at most `limit` children at a time, in order.
+    /// The rest wait for the running ones to complete.
+    pub fn with_limit(mut self, limit: NonZeroUsize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+    fn exit_children(&self, world: &mut World, entity: Entity, children_status: Vec<NodeStatus>) {
+        self.children
+            .iter()
+            .zip(children_status)
+            .for_each(|(child, child_status)| {
+                if let NodeStatus::Pending(child_state) = child_status {
+                    child.force_exit(world, entity, child_state);
+                }
+            });
+    }
 }
 #[cfg_attr(feature = "serde", typetag::serde)]
 impl Node for Parallel {
@@ -41,44 +64,83 @@ impl Node for Parallel {
                 .iter()
                 .map(|_| NodeStatus::Beginning)
                 .collect(),
+            results: self.children.iter().map(|_| None).collect(),
         };
         self.resume(world, entity, Box::new(state))
     }
 
-    fn resume(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) -> NodeStatus {
-        let state = Self::downcast(state).expect("Invalid state.");
-        if let Some(result) = self.result_strategy.construct(state.results()) {
-            self.force_exit(world, entity, Box::new(state));
-            return NodeStatus::Complete(result);
+    fn resume(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        mut state: Box<dyn NodeState>,
+    ) -> NodeStatus {
+        let result = self.resume_in_place(world, entity, &mut state);
+        NodeStatus::from_resumed(result, state)
+    }
+
+    fn resume_in_place(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        state: &mut Box<dyn NodeState>,
+    ) -> Option<NodeResult> {
+        let state = Self::downcast_mut(state).expect("Invalid state.");
+        if let Some(result) = self.result_strategy.construct(&state.results) {
+            self.exit_children(world, entity, std::mem::take(&mut state.children_status));
+            return Some(result);
         }
-        let children_status = self
+        // Children begin in order, so the waiting ones come after the running ones.
+        let mut running = state
+            .children_status
+            .iter()
+            .filter(|status| matches!(status, NodeStatus::Pending(_)))
+            .count();
+        let children = self
             .children
             .iter()
-            .zip(state.children_status.into_iter())
-            .map(|(child, child_status)| match child_status {
-                NodeStatus::Beginning => child.begin(world, entity),
-                NodeStatus::Pending(child_state) => child.resume(world, entity, child_state),
-                NodeStatus::Complete(_) => child_status,
-            })
-            .collect();
-        let state = ParallelState { children_status };
-        if let Some(result) = self.result_strategy.construct(state.results()) {
-            self.force_exit(world, entity, Box::new(state));
-            NodeStatus::Complete(result)
-        } else {
-            NodeStatus::Pending(Box::new(state))
+            .zip(state.children_status.iter_mut())
+            .zip(state.results.iter_mut());
+        for ((child, child_status), child_result) in children {
+            match child_status {
+                NodeStatus::Beginning if self.limit.is_some_and(|limit| limit.get() <= running) => {
+                }
+                NodeStatus::Beginning => {
+                    *child_status = child.begin(world, entity);
+                    match child_status {
+                        NodeStatus::Pending(_) => running += 1,
+                        &mut NodeStatus::Complete(result) => *child_result = Some(result),
+                        NodeStatus::Beginning => {}
+                    }
+                }
+                NodeStatus::Pending(child_state) => {
+                    if let Some(result) = child.resume_in_place(world, entity, child_state) {
+                        *child_status = NodeStatus::Complete(result);
+                        *child_result = Some(result);
+                        running -= 1;
+                    }
+                }
+                NodeStatus::Complete(_) => {}
+            }
         }
+        let result = self.result_strategy.construct(&state.results);
+        if result.is_some() {
+            self.exit_children(world, entity, std::mem::take(&mut state.children_status));
+        }
+        result
     }
 
     fn force_exit(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) {
         let state = Self::downcast(state).expect("Invalid state.");
-        self.children
-            .iter()
-            .zip(state.children_status.into_iter())
-            .for_each(|(child, child_status)| match child_status {
-                NodeStatus::Pending(child_state) => child.force_exit(world, entity, child_state),
-                _ => {}
-            });
+        self.exit_children(world, entity, state.children_status);
+    }
+
+    fn flatten(self: Box<Self>) -> FlatNode {
+        FlatNode::Parallel {
+            children: self.children,
+            result_strategy: self.result_strategy,
+            limit: self.limit,
+        }
     }
 }
 
@@ -87,15 +149,149 @@ impl Node for Parallel {
 #[derive(NodeState, Debug)]
 struct ParallelState {
     children_status: Vec<NodeStatus>,
+    /// Results of the children, kept along the status to pass to the strategy.
+    results: Vec<Option<NodeResult>>,
 }
-impl ParallelState {
-    fn results(&self) -> Vec<Option<NodeResult>> {
-        self.children_status
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, num::NonZeroUsize};
+
+    use crate::parallel::variants::JoinResultStrategy;
+    use crate::tester_util::prelude::*;
+
+    #[test]
+    fn test_limit() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let parallel = Parallel::new(
+            vec![
+                Box::new(TesterTask0::new(1, NodeResult::Success)),
+                Box::new(TesterTask1::new(2, NodeResult::Success)),
+                Box::new(TesterTask2::new(1, NodeResult::Success)),
+                Box::new(TesterTask3::new(1, NodeResult::Success)),
+            ],
+            JoinResultStrategy,
+        )
+        .with_limit(NonZeroUsize::new(2).unwrap());
+        let tree = BehaviorTree::from_node(
+            parallel,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        app.update();
+        app.update(); // 0, 1
+        app.update(); // 1, 2
+        app.update(); // 3
+        app.update(); // parallel completed
+        // Order of the log entries within same frame may change.
+        let expected: HashSet<(u32, u32, u32)> =
+            [(0, 0, 1), (1, 0, 1), (1, 1, 2), (2, 0, 2), (3, 0, 3)]
+                .into_iter()
+                .collect();
+        let found: HashSet<(u32, u32, u32)> = app
+            .world()
+            .resource::<TestLog>()
+            .log
             .iter()
-            .map(|status| match status {
-                &NodeStatus::Complete(result) => Some(result),
-                _ => None,
-            })
-            .collect()
+            .map(|entry| (entry.task_id, entry.updated_count, entry.frame))
+            .collect();
+        assert!(
+            found == expected,
+            "Parallel should run at most the limit of children at a time. found: {:?}",
+            found
+        );
+        let status = app.world().get::<TreeStatus>(entity).unwrap();
+        assert!(
+            status.result() == Some(NodeResult::Success),
+            "Parallel should complete after the queued children. found: {:?}",
+            status
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_zero_limit_rejected() {
+        let found = ron::from_str::<Box<dyn Node>>(
+            r#"{"type": "Parallel", "children": [], "result_strategy": {"type": "JoinResultStrategy"}, "limit": Some(0)}"#,
+        );
+        assert!(
+            found.is_err(),
+            "Limit of 0 should fail to deserialize. found: {:?}",
+            found
+        );
+        let found = ron::from_str::<Box<dyn Node>>(
+            r#"{"type": "Parallel", "children": [], "result_strategy": {"type": "JoinResultStrategy"}, "limit": Some(1)}"#,
+        );
+        assert!(
+            found.is_ok(),
+            "Positive limit should deserialize. found: {:?}",
+            found
+        );
+    }
+
+    /// Node that completes on the second resume, only in place.
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[derive(Debug)]
+    #[with_state(InPlaceState)]
+    struct InPlaceNode;
+    #[cfg_attr(feature = "serde", typetag::serde)]
+    impl Node for InPlaceNode {
+        fn begin(&self, _world: &mut World, _entity: Entity) -> NodeStatus {
+            NodeStatus::Pending(Box::new(InPlaceState { count: 0 }))
+        }
+        fn resume(
+            &self,
+            _world: &mut World,
+            _entity: Entity,
+            _state: Box<dyn NodeState>,
+        ) -> NodeStatus {
+            panic!("InPlaceNode should be resumed in place.")
+        }
+        fn resume_in_place(
+            &self,
+            _world: &mut World,
+            _entity: Entity,
+            state: &mut Box<dyn NodeState>,
+        ) -> Option<NodeResult> {
+            let state = Self::downcast_mut(state).expect("Invalid state.");
+            state.count += 1;
+            (2 <= state.count).then_some(NodeResult::Success)
+        }
+        fn force_exit(&self, _world: &mut World, _entity: Entity, _state: Box<dyn NodeState>) {}
+    }
+
+    /// State for [`InPlaceNode`]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[derive(NodeState, Debug)]
+    struct InPlaceState {
+        count: u32,
+    }
+
+    #[test]
+    fn test_resume_in_place() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let parallel = Parallel::new(
+            vec![
+                Box::new(InPlaceNode),
+                Box::new(TesterTask0::new(1, NodeResult::Success)),
+            ],
+            JoinResultStrategy,
+        );
+        let tree = BehaviorTree::from_node(
+            parallel,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        app.update();
+        app.update(); // 0, in place 1
+        app.update(); // in place 2, parallel completed
+        let status = app.world().get::<TreeStatus>(entity).unwrap();
+        assert!(
+            status.result() == Some(NodeResult::Success),
+            "Parallel should resume the children in place along with the others. found: {:?}",
+            status
+        );
     }
 }
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use crate as bevior_tree;
 use crate::node::prelude::*;
 
@@ -5,7 +7,10 @@ use super::Parallel;
 use crate::sequential::variants::{AndResultStrategy, OrResultStrategy};
 
 pub mod prelude {
-    pub use super::{Join, ParallelAnd, ParallelOr};
+    pub use super::{
+        Join, ParallelAnd, ParallelAtLeast, ParallelOr, ParallelThreshold, Race, ThresholdRatio,
+        ThresholdRatioError,
+    };
 }
 
 /// Node that runs children in parallel.
@@ -43,7 +48,7 @@ impl ParallelOr {
 pub struct JoinResultStrategy;
 #[cfg_attr(feature = "serde", typetag::serde)]
 impl crate::sequential::ResultStrategy for JoinResultStrategy {
-    fn construct(&self, results: Vec<Option<NodeResult>>) -> Option<NodeResult> {
+    fn construct(&self, results: &[Option<NodeResult>]) -> Option<NodeResult> {
         if results.contains(&None) {
             None
         } else {
@@ -66,6 +71,161 @@ impl Join {
     }
 }
 
+/// Completes with `result` when `count` of the children completed with it,
+///  or with the opposite when it became impossible.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+pub struct AtLeastResultStrategy {
+    pub result: NodeResult,
+    pub count: usize,
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl crate::sequential::ResultStrategy for AtLeastResultStrategy {
+    fn construct(&self, results: &[Option<NodeResult>]) -> Option<NodeResult> {
+        count_result(results, self.result, self.count)
+    }
+}
+
+/// Completes with `result` when more than `ratio` of the children completed with it,
+///  or with the opposite when it became impossible.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+pub struct ThresholdResultStrategy {
+    pub result: NodeResult,
+    pub ratio: ThresholdRatio,
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl crate::sequential::ResultStrategy for ThresholdResultStrategy {
+    fn construct(&self, results: &[Option<NodeResult>]) -> Option<NodeResult> {
+        let count = (results.len() as f32 * self.ratio.get()).floor() as usize + 1;
+        count_result(results, self.result, count)
+    }
+}
+
+/// Ratio of [`ThresholdResultStrategy`], in `[0, 1)` so that at least one and at most all the children are needed.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "f32", into = "f32"))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThresholdRatio(f32);
+impl ThresholdRatio {
+    /// Rejects NaN and the ratio out of `[0, 1)`.
+    pub fn new(ratio: f32) -> Result<Self, ThresholdRatioError> {
+        if (0.0..1.0).contains(&ratio) {
+            Ok(Self(ratio))
+        } else {
+            Err(ThresholdRatioError(ratio))
+        }
+    }
+    pub fn get(&self) -> f32 {
+        self.0
+    }
+}
+impl TryFrom<f32> for ThresholdRatio {
+    type Error = ThresholdRatioError;
+    fn try_from(ratio: f32) -> Result<Self, Self::Error> {
+        Self::new(ratio)
+    }
+}
+impl From<ThresholdRatio> for f32 {
+    fn from(ratio: ThresholdRatio) -> Self {
+        ratio.0
+    }
+}
+
+/// Ratio of [`ThresholdRatio`] out of `[0, 1)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThresholdRatioError(pub f32);
+impl fmt::Display for ThresholdRatioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Threshold ratio should be in [0, 1), found: {}", self.0)
+    }
+}
+impl std::error::Error for ThresholdRatioError {}
+
+fn count_result(
+    results: &[Option<NodeResult>],
+    result: NodeResult,
+    count: usize,
+) -> Option<NodeResult> {
+    let matched = results.iter().filter(|r| **r == Some(result)).count();
+    let pending = results.iter().filter(|r| r.is_none()).count();
+    if count <= matched {
+        Some(result)
+    } else if matched + pending < count {
+        Some(!result)
+    } else {
+        None
+    }
+}
+
+/// Completes with the result of the first child completed, the one with the lower index on ties.
+/// Failure if there are no children.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+pub struct RaceResultStrategy;
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl crate::sequential::ResultStrategy for RaceResultStrategy {
+    fn construct(&self, results: &[Option<NodeResult>]) -> Option<NodeResult> {
+        if results.is_empty() {
+            return Some(NodeResult::Failure);
+        }
+        results.iter().flatten().next().copied()
+    }
+}
+
+/// Node that runs children in parallel.
+/// When `count` of the children completed with `result`,
+///  abort the rest and returns it.
+/// Returns the opposite as soon as it became impossible.
+#[delegate_node(delegate)]
+pub struct ParallelAtLeast {
+    delegate: Parallel,
+}
+impl ParallelAtLeast {
+    pub fn new(nodes: Vec<Box<dyn Node>>, result: NodeResult, count: usize) -> Self {
+        Self {
+            delegate: Parallel::new(nodes, AtLeastResultStrategy { result, count }),
+        }
+    }
+}
+
+/// Node that runs children in parallel.
+/// When more than `ratio` of the children completed with `result`,
+///  abort the rest and returns it.
+/// Returns the opposite as soon as it became impossible.
+#[delegate_node(delegate)]
+pub struct ParallelThreshold {
+    delegate: Parallel,
+}
+impl ParallelThreshold {
+    /// Fails if `ratio` is NaN or out of `[0, 1)`.
+    pub fn new(
+        nodes: Vec<Box<dyn Node>>,
+        result: NodeResult,
+        ratio: f32,
+    ) -> Result<Self, ThresholdRatioError> {
+        let ratio = ThresholdRatio::new(ratio)?;
+        Ok(Self {
+            delegate: Parallel::new(nodes, ThresholdResultStrategy { result, ratio }),
+        })
+    }
+}
+
+/// Node that runs children in parallel.
+/// When one of the children completed,
+///  abort the rest and returns its result.
+#[delegate_node(delegate)]
+pub struct Race {
+    delegate: Parallel,
+}
+impl Race {
+    pub fn new(nodes: Vec<Box<dyn Node>>) -> Self {
+        Self {
+            delegate: Parallel::new(nodes, RaceResultStrategy),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
@@ -332,4 +492,156 @@ mod tests {
             found
         );
     }
+
+    /// Runs the tree until it completes, then checks the result and that no task is left running.
+    fn assert_completes(node: impl Node, expected: NodeResult, name: &str) {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let tree = BehaviorTree::from_node(
+            node,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        for _ in 0..8 {
+            app.update();
+        }
+        let status = app.world().get::<TreeStatus>(entity).unwrap();
+        assert!(
+            status.result() == Some(expected),
+            "{} should complete with {:?}. found: {:?}",
+            name,
+            expected,
+            status
+        );
+        let entity_ref = app.world().entity(entity);
+        assert!(
+            !entity_ref.contains::<TesterComponent<0>>()
+                && !entity_ref.contains::<TesterComponent<1>>()
+                && !entity_ref.contains::<TesterComponent<2>>()
+                && !entity_ref.contains::<TesterComponent<3>>(),
+            "{} should force exit the rest of the children.",
+            name
+        );
+    }
+
+    #[test]
+    fn test_at_least() {
+        assert_completes(
+            ParallelAtLeast::new(
+                vec![
+                    Box::new(TesterTask0::new(1, NodeResult::Success)),
+                    Box::new(TesterTask1::new(1, NodeResult::Failure)),
+                    Box::new(TesterTask2::new(2, NodeResult::Success)),
+                    Box::new(TesterTask3::new(100, NodeResult::Success)),
+                ],
+                NodeResult::Success,
+                2,
+            ),
+            NodeResult::Success,
+            "ParallelAtLeast",
+        );
+        assert_completes(
+            ParallelAtLeast::new(
+                vec![
+                    Box::new(TesterTask0::new(1, NodeResult::Failure)),
+                    Box::new(TesterTask1::new(2, NodeResult::Failure)),
+                    Box::new(TesterTask2::new(2, NodeResult::Success)),
+                    Box::new(TesterTask3::new(100, NodeResult::Success)),
+                ],
+                NodeResult::Success,
+                3,
+            ),
+            NodeResult::Failure,
+            "ParallelAtLeast unreachable",
+        );
+    }
+
+    #[test]
+    fn test_threshold() {
+        assert_completes(
+            ParallelThreshold::new(
+                vec![
+                    Box::new(TesterTask0::new(1, NodeResult::Failure)),
+                    Box::new(TesterTask1::new(2, NodeResult::Failure)),
+                    Box::new(TesterTask2::new(3, NodeResult::Failure)),
+                    Box::new(TesterTask3::new(100, NodeResult::Success)),
+                ],
+                NodeResult::Failure,
+                0.5,
+            )
+            .unwrap(),
+            NodeResult::Failure,
+            "ParallelThreshold",
+        );
+    }
+
+    #[test]
+    fn test_threshold_edges() {
+        assert_completes(
+            ParallelThreshold::new(
+                vec![
+                    Box::new(TesterTask0::new(100, NodeResult::Success)),
+                    Box::new(TesterTask1::new(1, NodeResult::Success)),
+                ],
+                NodeResult::Success,
+                0.0,
+            )
+            .unwrap(),
+            NodeResult::Success,
+            "ParallelThreshold of 0",
+        );
+        assert_completes(
+            ParallelThreshold::new(
+                vec![
+                    Box::new(TesterTask0::new(1, NodeResult::Success)),
+                    Box::new(TesterTask1::new(2, NodeResult::Success)),
+                ],
+                NodeResult::Success,
+                0.99,
+            )
+            .unwrap(),
+            NodeResult::Success,
+            "ParallelThreshold below 1",
+        );
+        for ratio in [1.0, 1.5, -0.1, f32::NAN] {
+            let found = ParallelThreshold::new(vec![], NodeResult::Success, ratio);
+            assert!(
+                found.is_err(),
+                "Ratio {} should be rejected. found: {:?}",
+                ratio,
+                found
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_threshold_ratio_deserialize() {
+        let found = ron::from_str::<ThresholdRatio>("1.0");
+        assert!(
+            found.is_err(),
+            "Ratio of 1 should fail to deserialize. found: {:?}",
+            found
+        );
+        let found = ron::from_str::<ThresholdRatio>("0.0");
+        assert!(
+            found.as_ref().is_ok_and(|ratio| ratio.get() == 0.0),
+            "Ratio of 0 should deserialize. found: {:?}",
+            found
+        );
+    }
+
+    #[test]
+    fn test_race() {
+        assert_completes(
+            Race::new(vec![
+                Box::new(TesterTask0::new(100, NodeResult::Success)),
+                Box::new(TesterTask1::new(2, NodeResult::Failure)),
+                Box::new(TesterTask2::new(3, NodeResult::Success)),
+                Box::new(TesterTask3::new(100, NodeResult::Success)),
+            ]),
+            NodeResult::Failure,
+            "Race",
+        );
+    }
 }
@@ -0,0 +1,193 @@
+//! Keeps running trees consistent with their root assets, restarting them when the asset is modified.
+
+use std::sync::Arc;
+
+use bevy::{ecs::message::MessageCursor, platform::collections::HashMap, prelude::*};
+
+use crate::{BehaviorTree, BehaviorTreeRoot, Freeze, TreeStatus, node::Node, node::NodeStatus};
+
+pub mod prelude {
+    pub use super::ReloadPolicy;
+}
+
+/// What to do with the running trees when their root asset is modified, e.g. hot-reloaded.
+/// The running nodes are force exited against the old root in either case.
+///
+/// Insert as resource to set the default, or add to the entity with the BehaviorTree to override it.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Resource, Component, Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum ReloadPolicy {
+    /// Begin the new root on the next update.
+    #[default]
+    Restart,
+    /// Insert [`Freeze`], the new root begins when it is removed.
+    Freeze,
+}
+
+/// Root nodes that the running trees began with.
+/// Trees keep running on these until the modification of the asset is handled,
+///  because the state of pending nodes is only valid for the root that built it.
+#[derive(Resource, Default)]
+pub(crate) struct TreeRoots {
+    roots: HashMap<AssetId<BehaviorTreeRoot>, Arc<dyn Node>>,
+}
+impl TreeRoots {
+    /// Root node for the id, caching it from the assets if not yet.
+    pub(crate) fn get(world: &mut World, id: AssetId<BehaviorTreeRoot>) -> Option<Arc<dyn Node>> {
+        if let Some(node) = world
+            .get_resource::<TreeRoots>()
+            .and_then(|roots| roots.roots.get(&id))
+        {
+            return Some(node.clone());
+        }
+        let node = world
+            .get_resource::<Assets<BehaviorTreeRoot>>()?
+            .get(id)?
+            .node
+            .clone();
+        if let Some(mut roots) = world.get_resource_mut::<TreeRoots>() {
+            roots.roots.insert(id, node.clone());
+        }
+        Some(node)
+    }
+}
+
+/// Force exits the trees running on modified roots, then restarts or freezes them as [`ReloadPolicy`].
+#[allow(clippy::type_complexity)]
+pub(crate) fn reload_modified_roots(
+    world: &mut World,
+    mut cursor: Local<MessageCursor<AssetEvent<BehaviorTreeRoot>>>,
+    query: &mut QueryState<(Entity, &BehaviorTree, Option<&ReloadPolicy>), With<TreeStatus>>,
+) {
+    let Some(messages) = world.get_resource::<Messages<AssetEvent<BehaviorTreeRoot>>>() else {
+        return;
+    };
+    let events: Vec<_> = cursor.read(messages).copied().collect();
+    for event in events {
+        match event {
+            AssetEvent::Modified { id } => reload(world, query, id),
+            AssetEvent::Removed { id } | AssetEvent::Unused { id } => {
+                world.resource_mut::<TreeRoots>().roots.remove(&id);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn reload(
+    world: &mut World,
+    query: &mut QueryState<(Entity, &BehaviorTree, Option<&ReloadPolicy>), With<TreeStatus>>,
+    id: AssetId<BehaviorTreeRoot>,
+) {
+    let Some(old_root) = world.resource::<TreeRoots>().roots.get(&id).cloned() else {
+        // No tree has begun with the root yet.
+        return;
+    };
+    let new_root = world
+        .resource::<Assets<BehaviorTreeRoot>>()
+        .get(id)
+        .map(|root| root.node.clone());
+    if new_root.is_some_and(|new_root| Arc::ptr_eq(&new_root, &old_root)) {
+        return;
+    }
+    world.resource_mut::<TreeRoots>().roots.remove(&id);
+    let default_policy = world
+        .get_resource::<ReloadPolicy>()
+        .copied()
+        .unwrap_or_default();
+    let targets: Vec<_> = query
+        .iter(world)
+        .filter(|(_, tree, _)| tree.root.id() == id)
+        .map(|(entity, _, policy)| (entity, policy.copied().unwrap_or(default_policy)))
+        .collect();
+    for (entity, policy) in targets {
+        let Some(mut status) = world.get_mut::<TreeStatus>(entity) else {
+            continue;
+        };
+        let state = match std::mem::replace(&mut status.0, NodeStatus::Beginning) {
+            NodeStatus::Pending(state) => state,
+            other => {
+                status.0 = other;
+                continue;
+            }
+        };
+        old_root.force_exit(world, entity, state);
+        match policy {
+            ReloadPolicy::Restart => {
+                crate::events::notify(world, crate::events::TreeRestarted { entity });
+            }
+            ReloadPolicy::Freeze => {
+                world.entity_mut(entity).insert(Freeze);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+    use crate::tester_util::prelude::*;
+
+    fn spawn_and_modify(app: &mut App, policy: Option<ReloadPolicy>) -> Entity {
+        let task = TesterTask0::new(5, NodeResult::Success);
+        let tree = BehaviorTree::from_node(
+            task,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let id = tree.root.id();
+        let entity = app.world_mut().spawn(tree).id();
+        if let Some(policy) = policy {
+            app.world_mut().entity_mut(entity).insert(policy);
+        }
+        app.update();
+        app.update(); // 0
+        let new_root = BehaviorTreeRoot {
+            node: Arc::new(TesterTask1::new(5, NodeResult::Success)),
+        };
+        app.world_mut()
+            .resource_mut::<Assets<BehaviorTreeRoot>>()
+            .insert(id, new_root)
+            .expect("Failed to modify the asset.");
+        app.update(); // 0, reloaded
+        app.update();
+        entity
+    }
+
+    #[test]
+    fn test_restart_on_modified() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let entity = spawn_and_modify(&mut app, None);
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_none(),
+            "Running task of the old root should exit on reload."
+        );
+        assert!(
+            app.world().get::<TesterComponent<1>>(entity).is_some(),
+            "New root should begin on reload."
+        );
+    }
+
+    #[test]
+    fn test_freeze_on_modified() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let entity = spawn_and_modify(&mut app, Some(ReloadPolicy::Freeze));
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_none(),
+            "Running task of the old root should exit on reload."
+        );
+        assert!(
+            app.world().get::<Freeze>(entity).is_some()
+                && app.world().get::<TesterComponent<1>>(entity).is_none(),
+            "Tree should be frozen on reload."
+        );
+        app.world_mut().entity_mut(entity).remove::<Freeze>();
+        app.update();
+        assert!(
+            app.world().get::<TesterComponent<1>>(entity).is_some(),
+            "New root should begin on unfreeze."
+        );
+    }
+}
@@ -0,0 +1,280 @@
+//! Utility selector that keeps scoring the children while running.
+
+use std::sync::Mutex;
+
+use bevy::ecs::{entity::Entity, world::World};
+
+use super::{Scorer, ScorerBuilder};
+use crate::node::{VacantState, prelude::*};
+use crate::timer::{Period, Stopwatch, TimeSource};
+
+pub mod prelude {
+    pub use super::DynamicScoredSelector;
+}
+
+/// Node that runs the child with the highest score, re-scoring the children every `interval`.
+/// Switches to the new best child, force exiting the running one,
+///  if its score exceeds the running one by more than `hysteresis`
+///  and the running one has run for `commitment` at least.
+///
+/// Completes with the result of the running child. Failure if there are no children.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+#[with_state(DynamicScoredSelectorState)]
+pub struct DynamicScoredSelector {
+    children: Vec<(Box<dyn Node>, Box<dyn ScorerBuilder>)>,
+    interval: Period,
+    hysteresis: f32,
+    commitment: Period,
+    time: TimeSource,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    scorers_runtime: Mutex<Vec<Box<Scorer>>>,
+}
+impl DynamicScoredSelector {
+    /// Re-scores on every update, switches whenever the best child changes.
+    pub fn new(children: Vec<(Box<dyn Node>, Box<dyn ScorerBuilder>)>) -> Self {
+        Self {
+            children,
+            interval: Period::Frames(1),
+            hysteresis: 0.0,
+            commitment: Period::Frames(0),
+            time: TimeSource::default(),
+            scorers_runtime: Mutex::new(Vec::new()),
+        }
+    }
+    /// Re-scores once in the period.
+    pub fn with_interval(mut self, interval: Period) -> Self {
+        self.interval = interval;
+        self
+    }
+    /// Margin that the new best score must exceed the running one by.
+    pub fn with_hysteresis(mut self, hysteresis: f32) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+    /// Minimum period to run the child before switching to another.
+    pub fn with_commitment(mut self, commitment: Period) -> Self {
+        self.commitment = commitment;
+        self
+    }
+    /// Reads the given clock rather than default [`TimeSource::Virtual`].
+    pub fn with_time(mut self, time: TimeSource) -> Self {
+        self.time = time;
+        self
+    }
+    fn scores(&self, world: &mut World, entity: Entity) -> Vec<f32> {
+        let mut scorers_runtime = self.scorers_runtime.lock().expect("Failed to lock");
+        if scorers_runtime.is_empty() {
+            *scorers_runtime = self
+                .children
+                .iter()
+                .map(|(_, builder)| {
+                    let mut scorer = builder.build();
+                    scorer.initialize(world);
+                    scorer
+                })
+                .collect();
+        }
+        scorers_runtime
+            .iter_mut()
+            .map(|scorer| scorer.run(entity, world).expect("Scorer failed"))
+            .collect()
+    }
+    /// Index of the highest score, the first one on ties.
+    fn best(scores: &[f32]) -> Option<usize> {
+        scores
+            .iter()
+            .enumerate()
+            .fold(
+                None,
+                |best: Option<(usize, f32)>, (index, &score)| match best {
+                    Some((_, best_score)) if best_score >= score => best,
+                    _ => Some((index, score)),
+                },
+            )
+            .map(|(index, _)| index)
+    }
+    fn switch(&self, world: &mut World, entity: Entity, index: usize) -> NodeStatus {
+        let status = self.children[index].0.begin(world, entity);
+        match status {
+            NodeStatus::Pending(child_state) => {
+                NodeStatus::Pending(Box::new(DynamicScoredSelectorState {
+                    index,
+                    child_state,
+                    rescore: Stopwatch::start(self.time, world),
+                    commitment: Stopwatch::start(self.time, world),
+                }))
+            }
+            _ => status,
+        }
+    }
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl Node for DynamicScoredSelector {
+    fn begin(&self, world: &mut World, entity: Entity) -> NodeStatus {
+        let scores = self.scores(world, entity);
+        match Self::best(&scores) {
+            Some(index) => self.switch(world, entity, index),
+            None => NodeStatus::Complete(NodeResult::Failure),
+        }
+    }
+
+    fn resume(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        mut state: Box<dyn NodeState>,
+    ) -> NodeStatus {
+        let result = self.resume_in_place(world, entity, &mut state);
+        NodeStatus::from_resumed(result, state)
+    }
+
+    fn resume_in_place(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        state: &mut Box<dyn NodeState>,
+    ) -> Option<NodeResult> {
+        let inner = Self::downcast_mut(state).expect("Invalid state.");
+        inner.rescore.tick(self.time, world);
+        inner.commitment.tick(self.time, world);
+        if inner.rescore.reached(self.interval) && inner.commitment.reached(self.commitment) {
+            inner.rescore = Stopwatch::start(self.time, world);
+            let scores = self.scores(world, entity);
+            if let Some(best) = Self::best(&scores)
+                && best != inner.index
+                && scores[inner.index] + self.hysteresis < scores[best]
+            {
+                let child_state = VacantState::take(&mut inner.child_state);
+                self.children[inner.index]
+                    .0
+                    .force_exit(world, entity, child_state);
+                return self.switch(world, entity, best).into_resumed(state);
+            }
+        }
+        self.children[inner.index]
+            .0
+            .resume_in_place(world, entity, &mut inner.child_state)
+    }
+
+    fn force_exit(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) {
+        let state = Self::downcast(state).expect("Invalid state.");
+        self.children[state.index]
+            .0
+            .force_exit(world, entity, state.child_state);
+    }
+}
+
+/// State for [`DynamicScoredSelector`]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(NodeState, Debug)]
+struct DynamicScoredSelectorState {
+    index: usize,
+    child_state: Box<dyn NodeState>,
+    /// Since the last scoring.
+    rescore: Stopwatch,
+    /// Since the running child began.
+    commitment: Stopwatch,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tester_util::prelude::*;
+
+    #[derive(Component)]
+    struct TestScores(Vec<f32>);
+
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[derive(Debug)]
+    struct TestScorerBuilder {
+        index: usize,
+    }
+    #[cfg_attr(feature = "serde", typetag::serde)]
+    impl ScorerBuilder for TestScorerBuilder {
+        fn build(&self) -> Box<Scorer> {
+            let index = self.index;
+            Box::new(IntoSystem::into_system(
+                move |In(entity): In<Entity>, query: Query<&TestScores>| -> f32 {
+                    query.get(entity).map_or(0.0, |scores| scores.0[index])
+                },
+            ))
+        }
+    }
+
+    fn spawn_selector(app: &mut App, selector: DynamicScoredSelector, scores: Vec<f32>) -> Entity {
+        let tree = BehaviorTree::from_node(
+            selector,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        app.world_mut().spawn((tree, TestScores(scores))).id()
+    }
+
+    fn children() -> Vec<(Box<dyn Node>, Box<dyn ScorerBuilder>)> {
+        vec![
+            (
+                Box::new(TesterTask0::new(10, NodeResult::Success)),
+                Box::new(TestScorerBuilder { index: 0 }),
+            ),
+            (
+                Box::new(TesterTask1::new(10, NodeResult::Success)),
+                Box::new(TestScorerBuilder { index: 1 }),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_dynamic_scored_selector_switch() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let entity = spawn_selector(
+            &mut app,
+            DynamicScoredSelector::new(children()).with_hysteresis(0.1),
+            vec![1.0, 0.0],
+        );
+        app.update();
+        app.update(); // 0
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_some(),
+            "Best child should run."
+        );
+        app.world_mut().get_mut::<TestScores>(entity).unwrap().0 = vec![1.0, 1.05];
+        app.update(); // within hysteresis, 0
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_some(),
+            "Selector should not switch within the hysteresis."
+        );
+        app.world_mut().get_mut::<TestScores>(entity).unwrap().0 = vec![1.0, 2.0];
+        app.update(); // switch to 1
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_none()
+                && app.world().get::<TesterComponent<1>>(entity).is_some(),
+            "Selector should force exit the running child and switch to the new best."
+        );
+    }
+
+    #[test]
+    fn test_dynamic_scored_selector_commitment() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let entity = spawn_selector(
+            &mut app,
+            DynamicScoredSelector::new(children()).with_commitment(Period::Frames(3)),
+            vec![1.0, 0.0],
+        );
+        app.update(); // begin 0
+        app.world_mut().get_mut::<TestScores>(entity).unwrap().0 = vec![0.0, 1.0];
+        app.update(); // committed, 0
+        app.update(); // committed, 0
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_some(),
+            "Selector should keep the child within the commitment."
+        );
+        app.update(); // switch to 1
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_none()
+                && app.world().get::<TesterComponent<1>>(entity).is_some(),
+            "Selector should switch after the commitment."
+        );
+    }
+}
@@ -8,14 +8,16 @@ use bevy::ecs::{
     world::World,
 };
 
-use crate::node::prelude::*;
+use crate::{compiled::FlatNode, node::prelude::*};
 
+pub mod dynamic;
+pub mod scorer;
 pub mod variants;
 
 pub mod prelude {
     pub use super::{
         Picker, PickerBuilder, ResultStrategy, ScoredSequence, Scorer, ScorerBuilder,
-        variants::prelude::*,
+        dynamic::prelude::*, scorer::prelude::*, variants::prelude::*,
     };
 }
 
@@ -33,9 +35,14 @@ pub trait PickerBuilder: 'static + Debug + Send + Sync {
     fn build(&self) -> Box<Picker>;
 }
 
+/// Decides the result of the composite node from the results of its children, `None` for the pending ones.
+///
+/// `construct` takes the results as a slice, borrowed from the state of the node.
+/// To migrate a strategy taking `Vec<Option<NodeResult>>`, change the parameter to `&[Option<NodeResult>]`,
+///  using `iter()` instead of `into_iter()` where the results are consumed.
 #[cfg_attr(feature = "serde", typetag::serde(tag = "type"))]
 pub trait ResultStrategy: 'static + Debug + Send + Sync {
-    fn construct(&self, results: Vec<Option<NodeResult>>) -> Option<NodeResult>;
+    fn construct(&self, results: &[Option<NodeResult>]) -> Option<NodeResult>;
 }
 
 /// Composite nodes that run children in sequence.
@@ -47,9 +54,7 @@ pub struct ScoredSequence {
     picker: Box<dyn PickerBuilder>,
     result_strategy: Box<dyn ResultStrategy>,
     #[cfg_attr(feature = "serde", serde(skip))]
-    scorers_runtime: Mutex<Vec<Box<Scorer>>>,
-    #[cfg_attr(feature = "serde", serde(skip))]
-    picker_runtime: Mutex<Option<Box<Picker>>>,
+    runtime: PickerRuntime,
 }
 impl ScoredSequence {
     pub fn new(
@@ -61,99 +66,127 @@ impl ScoredSequence {
             children,
             picker: Box::new(picker),
             result_strategy: Box::new(result_strategy),
-            scorers_runtime: Mutex::new(Vec::new()),
-            picker_runtime: Mutex::new(None),
-        }
-    }
-    fn init(&self, world: &mut World) {
-        let mut scorers_runtime = self.scorers_runtime.lock().expect("Failed to lock");
-        if scorers_runtime.is_empty() {
-            *scorers_runtime = self
-                .children
-                .iter()
-                .map(|(_, builder)| {
-                    let mut scorer = builder.build();
-                    scorer.initialize(world);
-                    scorer
-                })
-                .collect();
-        }
-        let mut picker_runtime = self.picker_runtime.lock().expect("Failed to lock");
-        if picker_runtime.is_none() {
-            let mut picker = self.picker.build();
-            picker.initialize(world);
-            *picker_runtime = Some(picker);
+            runtime: PickerRuntime::default(),
         }
     }
 }
 #[cfg_attr(feature = "serde", typetag::serde)]
 impl Node for ScoredSequence {
     fn begin(&self, world: &mut World, entity: Entity) -> NodeStatus {
-        self.init(world);
-        let scores = self
-            .scorers_runtime
-            .lock()
-            .expect("Failed to lock")
-            .iter_mut()
-            .map(|scorer| scorer.run(entity, world).expect("Scorer failed"))
-            .collect();
-        let mut picker_lock = self.picker_runtime.lock().expect("Failed to lock");
-        let indices = picker_lock
-            .as_mut()
-            .expect("Picker not initialized")
-            .run((scores, entity), world)
-            .expect("Picker failed");
+        let indices = self.runtime.pick(
+            world,
+            entity,
+            self.children.iter().map(|(_, scorer)| scorer.as_ref()),
+            self.picker.as_ref(),
+        );
         let state = Box::new(ScoredSequenceState::new(indices));
         self.resume(world, entity, state)
     }
 
     fn resume(
         &self,
-        world: &mut bevy::prelude::World,
+        world: &mut World,
         entity: Entity,
-        state: Box<dyn NodeState>,
+        mut state: Box<dyn NodeState>,
     ) -> NodeStatus {
-        let state = Self::downcast(state).expect("Invalid state.");
-        let Some(&index) = state.indices.iter().skip(state.count).next() else {
-            // All the nodes are completed.
-            let Some(result) = self.result_strategy.construct(state.results) else {
-                panic!("Result constructor returned None on the end.");
+        let result = self.resume_in_place(world, entity, &mut state);
+        NodeStatus::from_resumed(result, state)
+    }
+
+    fn resume_in_place(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        state: &mut Box<dyn NodeState>,
+    ) -> Option<NodeResult> {
+        let state = Self::downcast_mut(state).expect("Invalid state.");
+        loop {
+            let Some(&index) = state.indices.get(state.count) else {
+                // All the nodes are completed.
+                let Some(result) = self.result_strategy.construct(&state.results) else {
+                    panic!("Result constructor returned None on the end.");
+                };
+                return Some(result);
             };
-            return NodeStatus::Complete(result);
-        };
-        let (state, child_state) = state.extract_child_state();
-        let node = &self.children[index].0;
-        let child_status = match child_state {
-            None => node.begin(world, entity),
-            Some(s) => node.resume(world, entity, s),
-        };
-        match child_status {
-            NodeStatus::Pending(child_state) => {
-                NodeStatus::Pending(Box::new(state.update_pending(child_state)))
-            }
-            NodeStatus::Complete(child_result) => {
-                let state = state.update_result(child_result);
-                let result = self.result_strategy.construct(state.results.clone());
-                match result {
-                    Some(result) => NodeStatus::Complete(result),
-                    None => self.resume(world, entity, Box::new(state)),
-                }
+            let node = &self.children[index].0;
+            let child_result = match state.child_state.as_mut() {
+                Some(child_state) => node.resume_in_place(world, entity, child_state),
+                None => match node.begin(world, entity) {
+                    NodeStatus::Pending(child_state) => {
+                        state.child_state = Some(child_state);
+                        None
+                    }
+                    NodeStatus::Complete(child_result) => Some(child_result),
+                    NodeStatus::Beginning => panic!("Unexpected NodeStatus::Beginning."),
+                },
+            };
+            // Pending child keeps its state in place.
+            let child_result = child_result?;
+            state.update_result(child_result);
+            if let Some(result) = self.result_strategy.construct(&state.results) {
+                return Some(result);
             }
-            NodeStatus::Beginning => panic!("Unexpected NodeStatus::Beginning."),
         }
     }
 
     fn force_exit(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) {
         let state = Self::downcast(state).expect("Invalid state.");
-        let Some(&index) = state.indices.iter().skip(state.count).next() else {
-            return;
-        };
-        let (_, Some(child_state)) = state.extract_child_state() else {
+        let (Some(&index), Some(child_state)) = (state.indices.get(state.count), state.child_state)
+        else {
             return;
         };
         let node = &self.children[index].0;
         node.force_exit(world, entity, child_state)
     }
+
+    fn flatten(self: Box<Self>) -> FlatNode {
+        FlatNode::Sequential {
+            children: self.children,
+            picker: self.picker,
+            result_strategy: self.result_strategy,
+        }
+    }
+}
+
+/// Scorers and picker of the sequence, built on the first use.
+#[derive(Debug, Default)]
+pub(crate) struct PickerRuntime {
+    scorers: Mutex<Vec<Box<Scorer>>>,
+    picker: Mutex<Option<Box<Picker>>>,
+}
+impl PickerRuntime {
+    /// Scores the children, then picks the indices of them to run in order.
+    pub(crate) fn pick<'a>(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        scorers: impl Iterator<Item = &'a dyn ScorerBuilder>,
+        picker: &dyn PickerBuilder,
+    ) -> Vec<usize> {
+        let mut scorers_runtime = self.scorers.lock().expect("Failed to lock");
+        if scorers_runtime.is_empty() {
+            *scorers_runtime = scorers
+                .map(|builder| {
+                    let mut scorer = builder.build();
+                    scorer.initialize(world);
+                    scorer
+                })
+                .collect();
+        }
+        let scores = scorers_runtime
+            .iter_mut()
+            .map(|scorer| scorer.run(entity, world).expect("Scorer failed"))
+            .collect();
+        let mut picker_runtime = self.picker.lock().expect("Failed to lock");
+        picker_runtime
+            .get_or_insert_with(|| {
+                let mut picker = picker.build();
+                picker.initialize(world);
+                picker
+            })
+            .run((scores, entity), world)
+            .expect("Picker failed")
+    }
 }
 
 /// State for [`ScoredSequence`]
@@ -175,37 +208,9 @@ impl ScoredSequenceState {
             child_state: None,
         }
     }
-    fn update_pending(self, child_state: Box<dyn NodeState>) -> Self {
-        Self {
-            count: self.count,
-            indices: self.indices,
-            results: self.results,
-            child_state: Some(child_state),
-        }
-    }
-    fn update_result(self, result: NodeResult) -> Self {
-        let results = self
-            .results
-            .into_iter()
-            .enumerate()
-            .map(|(index, v)| if index == self.count { Some(result) } else { v })
-            .collect();
-        Self {
-            count: self.count + 1,
-            indices: self.indices,
-            results,
-            child_state: None,
-        }
-    }
-    fn extract_child_state(self) -> (Self, Option<Box<dyn NodeState>>) {
-        (
-            Self {
-                count: self.count,
-                indices: self.indices,
-                results: self.results,
-                child_state: None,
-            },
-            self.child_state,
-        )
+    fn update_result(&mut self, result: NodeResult) {
+        self.results[self.count] = Some(result);
+        self.count += 1;
+        self.child_state = None;
     }
 }
@@ -0,0 +1,585 @@
+//! Scorers composed of the inputs, response curves and combinators, for utility AI in data.
+
+use std::fmt;
+
+use bevy::{
+    ecs::{
+        entity::Entity,
+        reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+        system::{In, IntoSystem},
+        world::World,
+    },
+    reflect::{GetPath, PartialReflect, Reflect},
+};
+
+use super::{Scorer, ScorerBuilder};
+use crate::blackboard::{Blackboard, BlackboardValue};
+
+pub mod prelude {
+    pub use super::{
+        CurveScorerBuilder, MaxScorerBuilder, MinScorerBuilder, PiecewiseError, PiecewisePoints,
+        ProductScorerBuilder, ResponseCurve, ScoreInput, WeightedSumScorerBuilder,
+    };
+}
+
+/// Value that the scorers read from the world.
+/// Numbers and booleans are read as f32, None if missing or of other types.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub enum ScoreInput {
+    /// Field of the component on the entity, by the reflect path like `"stats.health"`.
+    /// `type_path` is the full or the short type path of the component registered with `ReflectComponent`.
+    /// Empty `field` reads the component itself.
+    Component { type_path: String, field: String },
+    /// Field of the resource registered with `ReflectResource`, same as [`ScoreInput::Component`].
+    Resource { type_path: String, field: String },
+    /// Value on the [`Blackboard`] of the entity.
+    Blackboard(String),
+}
+impl ScoreInput {
+    pub fn component(type_path: impl Into<String>, field: impl Into<String>) -> Self {
+        Self::Component {
+            type_path: type_path.into(),
+            field: field.into(),
+        }
+    }
+    pub fn resource(type_path: impl Into<String>, field: impl Into<String>) -> Self {
+        Self::Resource {
+            type_path: type_path.into(),
+            field: field.into(),
+        }
+    }
+    pub fn blackboard(key: impl Into<String>) -> Self {
+        Self::Blackboard(key.into())
+    }
+    pub fn read(&self, world: &World, entity: Entity) -> Option<f32> {
+        match self {
+            Self::Component { type_path, field } => {
+                let registry = world.get_resource::<AppTypeRegistry>()?.read();
+                let registration = registry
+                    .get_with_type_path(type_path)
+                    .or_else(|| registry.get_with_short_type_path(type_path))?;
+                let component = registration
+                    .data::<ReflectComponent>()?
+                    .reflect(world.get_entity(entity).ok()?)?;
+                Self::number(Self::field(component, field)?)
+            }
+            Self::Resource { type_path, field } => {
+                let registry = world.get_resource::<AppTypeRegistry>()?.read();
+                let registration = registry
+                    .get_with_type_path(type_path)
+                    .or_else(|| registry.get_with_short_type_path(type_path))?;
+                let resource = registration
+                    .data::<ReflectResource>()?
+                    .reflect(world)
+                    .ok()?;
+                Self::number(Self::field(resource, field)?)
+            }
+            Self::Blackboard(key) => {
+                Self::number(world.get::<Blackboard>(entity)?.get_reflect(key)?)
+            }
+        }
+    }
+    fn field<'a>(value: &'a dyn Reflect, field: &str) -> Option<&'a dyn PartialReflect> {
+        if field.is_empty() {
+            Some(value.as_partial_reflect())
+        } else {
+            value.reflect_path(field).ok()
+        }
+    }
+    fn number(value: &dyn PartialReflect) -> Option<f32> {
+        match BlackboardValue::from_reflect(value)? {
+            BlackboardValue::Bool(value) => Some(if value { 1.0 } else { 0.0 }),
+            BlackboardValue::Int(value) => Some(value as f32),
+            BlackboardValue::Float(value) => Some(value as f32),
+            BlackboardValue::String(_) => None,
+        }
+    }
+}
+
+/// Function mapping the input to the score.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResponseCurve {
+    Constant(f32),
+    /// `slope * x + intercept`
+    Linear {
+        slope: f32,
+        intercept: f32,
+    },
+    /// `a * x^2 + b * x + c`
+    Quadratic {
+        a: f32,
+        b: f32,
+        c: f32,
+    },
+    /// `max / (1 + e^(-steepness * (x - midpoint)))`
+    Logistic {
+        max: f32,
+        steepness: f32,
+        midpoint: f32,
+    },
+    /// Interpolates linearly between the points, flat outside of them.
+    Piecewise(PiecewisePoints),
+}
+impl ResponseCurve {
+    /// [`ResponseCurve::Piecewise`] through the points in any order.
+    pub fn piecewise(points: Vec<(f32, f32)>) -> Result<Self, PiecewiseError> {
+        PiecewisePoints::new(points).map(Self::Piecewise)
+    }
+    pub fn evaluate(&self, x: f32) -> f32 {
+        match self {
+            Self::Constant(value) => *value,
+            Self::Linear { slope, intercept } => slope * x + intercept,
+            Self::Quadratic { a, b, c } => a * x * x + b * x + c,
+            Self::Logistic {
+                max,
+                steepness,
+                midpoint,
+            } => max / (1.0 + (-steepness * (x - midpoint)).exp()),
+            Self::Piecewise(PiecewisePoints(points)) => {
+                let Some(&(first_x, first_y)) = points.first() else {
+                    return 0.0;
+                };
+                if x <= first_x {
+                    return first_y;
+                }
+                points
+                    .windows(2)
+                    .find(|pair| x <= pair[1].0)
+                    .map(|pair| {
+                        let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+                        y0 + (y1 - y0) * (x - x0) / (x1 - x0)
+                    })
+                    .unwrap_or_else(|| points[points.len() - 1].1)
+            }
+        }
+    }
+}
+
+/// Points of [`ResponseCurve::Piecewise`], sorted by x without duplicates.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "Vec<(f32, f32)>", into = "Vec<(f32, f32)>")
+)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PiecewisePoints(Vec<(f32, f32)>);
+impl PiecewisePoints {
+    /// Sorts the points by x, rejecting NaN or duplicate x.
+    pub fn new(mut points: Vec<(f32, f32)>) -> Result<Self, PiecewiseError> {
+        if points.iter().any(|(x, _)| x.is_nan()) {
+            return Err(PiecewiseError::NanX);
+        }
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        if let Some(pair) = points.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+            return Err(PiecewiseError::DuplicateX(pair[0].0));
+        }
+        Ok(Self(points))
+    }
+    pub fn points(&self) -> &[(f32, f32)] {
+        &self.0
+    }
+}
+impl TryFrom<Vec<(f32, f32)>> for PiecewisePoints {
+    type Error = PiecewiseError;
+    fn try_from(points: Vec<(f32, f32)>) -> Result<Self, Self::Error> {
+        Self::new(points)
+    }
+}
+impl From<PiecewisePoints> for Vec<(f32, f32)> {
+    fn from(points: PiecewisePoints) -> Self {
+        points.0
+    }
+}
+
+/// Invalid points of [`ResponseCurve::Piecewise`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PiecewiseError {
+    NanX,
+    DuplicateX(f32),
+}
+impl fmt::Display for PiecewiseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NanX => write!(f, "Piecewise curve has a point with NaN x."),
+            Self::DuplicateX(x) => write!(f, "Piecewise curve has points with the same x: {}", x),
+        }
+    }
+}
+impl std::error::Error for PiecewiseError {}
+
+/// Scores by the curve over the input. Missing input is read as zero.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct CurveScorerBuilder {
+    pub input: ScoreInput,
+    pub curve: ResponseCurve,
+}
+impl CurveScorerBuilder {
+    pub fn new(input: ScoreInput, curve: ResponseCurve) -> Self {
+        Self { input, curve }
+    }
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl ScorerBuilder for CurveScorerBuilder {
+    fn build(&self) -> Box<Scorer> {
+        let builder = self.clone();
+        Box::new(IntoSystem::into_system(
+            move |In(entity): In<Entity>, world: &World| -> f32 {
+                let x = builder.input.read(world, entity).unwrap_or_default();
+                builder.curve.evaluate(x)
+            },
+        ))
+    }
+}
+
+/// Scorer running the child scorers, combining their scores with the function.
+fn combine(
+    builders: &[&dyn ScorerBuilder],
+    f: impl Fn(Vec<f32>) -> f32 + Send + Sync + 'static,
+) -> Box<Scorer> {
+    let mut scorers: Vec<Box<Scorer>> = builders.iter().map(|builder| builder.build()).collect();
+    let mut initialized = false;
+    Box::new(IntoSystem::into_system(
+        move |In(entity): In<Entity>, world: &mut World| -> f32 {
+            if !initialized {
+                scorers.iter_mut().for_each(|scorer| {
+                    scorer.initialize(world);
+                });
+                initialized = true;
+            }
+            let scores = scorers
+                .iter_mut()
+                .map(|scorer| scorer.run(entity, world).expect("Scorer failed"))
+                .collect();
+            f(scores)
+        },
+    ))
+}
+
+/// Multiplies the scores, 1 if empty.
+/// With the compensation, raises each score so that many considerations do not pull the product down.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+pub struct ProductScorerBuilder {
+    scorers: Vec<Box<dyn ScorerBuilder>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    compensation: bool,
+}
+impl ProductScorerBuilder {
+    pub fn new(scorers: Vec<Box<dyn ScorerBuilder>>) -> Self {
+        Self {
+            scorers,
+            compensation: false,
+        }
+    }
+    pub fn with_compensation(mut self) -> Self {
+        self.compensation = true;
+        self
+    }
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl ScorerBuilder for ProductScorerBuilder {
+    fn build(&self) -> Box<Scorer> {
+        let builders: Vec<_> = self.scorers.iter().map(AsRef::as_ref).collect();
+        let modification = if self.compensation {
+            1.0 - 1.0 / builders.len().max(1) as f32
+        } else {
+            0.0
+        };
+        combine(&builders, move |scores| {
+            scores
+                .into_iter()
+                .map(|score| score + (1.0 - score) * modification * score)
+                .product()
+        })
+    }
+}
+
+/// Lowest of the scores, 0 if empty.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+pub struct MinScorerBuilder {
+    scorers: Vec<Box<dyn ScorerBuilder>>,
+}
+impl MinScorerBuilder {
+    pub fn new(scorers: Vec<Box<dyn ScorerBuilder>>) -> Self {
+        Self { scorers }
+    }
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl ScorerBuilder for MinScorerBuilder {
+    fn build(&self) -> Box<Scorer> {
+        let builders: Vec<_> = self.scorers.iter().map(AsRef::as_ref).collect();
+        combine(&builders, |scores| {
+            scores.into_iter().reduce(f32::min).unwrap_or_default()
+        })
+    }
+}
+
+/// Highest of the scores, 0 if empty.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+pub struct MaxScorerBuilder {
+    scorers: Vec<Box<dyn ScorerBuilder>>,
+}
+impl MaxScorerBuilder {
+    pub fn new(scorers: Vec<Box<dyn ScorerBuilder>>) -> Self {
+        Self { scorers }
+    }
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl ScorerBuilder for MaxScorerBuilder {
+    fn build(&self) -> Box<Scorer> {
+        let builders: Vec<_> = self.scorers.iter().map(AsRef::as_ref).collect();
+        combine(&builders, |scores| {
+            scores.into_iter().reduce(f32::max).unwrap_or_default()
+        })
+    }
+}
+
+/// Sum of the scores multiplied by the weights.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+pub struct WeightedSumScorerBuilder {
+    scorers: Vec<(f32, Box<dyn ScorerBuilder>)>,
+}
+impl WeightedSumScorerBuilder {
+    pub fn new(scorers: Vec<(f32, Box<dyn ScorerBuilder>)>) -> Self {
+        Self { scorers }
+    }
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl ScorerBuilder for WeightedSumScorerBuilder {
+    fn build(&self) -> Box<Scorer> {
+        let weights: Vec<f32> = self.scorers.iter().map(|(weight, _)| *weight).collect();
+        let builders: Vec<_> = self
+            .scorers
+            .iter()
+            .map(|(_, builder)| builder.as_ref())
+            .collect();
+        combine(&builders, move |scores| {
+            scores.iter().zip(&weights).map(|(s, w)| s * w).sum()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::{component::Component, resource::Resource};
+
+    use super::*;
+    use crate::sequential::variants::ConstantScorerBuilder;
+
+    #[derive(Component, Reflect)]
+    #[reflect(Component)]
+    struct TestStats {
+        health: f32,
+        level: u32,
+    }
+
+    #[derive(Resource, Reflect)]
+    #[reflect(Resource)]
+    struct TestDanger(f64);
+
+    fn test_world() -> (World, Entity) {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let mut registry = world.resource::<AppTypeRegistry>().write();
+            registry.register::<TestStats>();
+            registry.register::<TestDanger>();
+        }
+        world.insert_resource(TestDanger(0.25));
+        let mut blackboard = Blackboard::default();
+        blackboard.set_reflect("alert", Box::new(true));
+        let entity = world
+            .spawn((
+                TestStats {
+                    health: 0.5,
+                    level: 3,
+                },
+                blackboard,
+            ))
+            .id();
+        (world, entity)
+    }
+
+    fn score(world: &mut World, entity: Entity, builder: &dyn ScorerBuilder) -> f32 {
+        let mut scorer = builder.build();
+        scorer.initialize(world);
+        scorer.run(entity, world).expect("Scorer failed")
+    }
+
+    #[test]
+    fn test_response_curves() {
+        let cases = [
+            (ResponseCurve::Constant(0.3), 5.0, 0.3),
+            (
+                ResponseCurve::Linear {
+                    slope: 2.0,
+                    intercept: 0.5,
+                },
+                0.25,
+                1.0,
+            ),
+            (
+                ResponseCurve::Quadratic {
+                    a: 1.0,
+                    b: 0.0,
+                    c: 0.1,
+                },
+                0.5,
+                0.35,
+            ),
+            (
+                ResponseCurve::Logistic {
+                    max: 1.0,
+                    steepness: 10.0,
+                    midpoint: 0.5,
+                },
+                0.5,
+                0.5,
+            ),
+            (
+                ResponseCurve::piecewise(vec![(2.0, 0.0), (0.0, 0.0), (1.0, 1.0)]).unwrap(),
+                1.5,
+                0.5,
+            ),
+            (
+                ResponseCurve::piecewise(vec![(0.0, 0.2), (1.0, 1.0)]).unwrap(),
+                -1.0,
+                0.2,
+            ),
+        ];
+        for (curve, x, expected) in cases {
+            let found = curve.evaluate(x);
+            assert!(
+                (found - expected).abs() < 1e-5,
+                "{:?} at {} should be {}. found: {}",
+                curve,
+                x,
+                expected,
+                found
+            );
+        }
+    }
+
+    #[test]
+    fn test_piecewise_duplicate_x() {
+        let found = ResponseCurve::piecewise(vec![(0.0, 0.0), (1.0, 1.0), (0.0, 0.5)]);
+        assert!(
+            found == Err(PiecewiseError::DuplicateX(0.0)),
+            "Points with the same x should be rejected. found: {:?}",
+            found
+        );
+        #[cfg(feature = "serde")]
+        {
+            let found = ron::from_str::<ResponseCurve>("Piecewise([(1.0, 0.0), (1.0, 1.0)])");
+            assert!(
+                found.is_err(),
+                "Points with the same x should fail to deserialize. found: {:?}",
+                found
+            );
+        }
+    }
+
+    #[test]
+    fn test_score_inputs() {
+        let (mut world, entity) = test_world();
+        let identity = ResponseCurve::Linear {
+            slope: 1.0,
+            intercept: 0.0,
+        };
+        let cases = [
+            (ScoreInput::component("TestStats", "health"), 0.5),
+            (ScoreInput::component("TestStats", "level"), 3.0),
+            (ScoreInput::resource("TestDanger", ".0"), 0.25),
+            (ScoreInput::blackboard("alert"), 1.0),
+            (ScoreInput::blackboard("missing"), 0.0),
+        ];
+        for (input, expected) in cases {
+            let found = score(
+                &mut world,
+                entity,
+                &CurveScorerBuilder::new(input.clone(), identity.clone()),
+            );
+            assert!(
+                (found - expected).abs() < 1e-5,
+                "{:?} should read {}. found: {}",
+                input,
+                expected,
+                found
+            );
+        }
+    }
+
+    #[test]
+    fn test_combinators() {
+        let (mut world, entity) = test_world();
+        let scorers = || -> Vec<Box<dyn ScorerBuilder>> {
+            vec![
+                Box::new(ConstantScorerBuilder::new(0.5)),
+                Box::new(CurveScorerBuilder::new(
+                    ScoreInput::component("TestStats", "health"),
+                    ResponseCurve::Linear {
+                        slope: 1.0,
+                        intercept: 0.3,
+                    },
+                )),
+            ]
+        };
+        let cases: [(Box<dyn ScorerBuilder>, f32); 5] = [
+            (Box::new(ProductScorerBuilder::new(scorers())), 0.4),
+            (
+                Box::new(ProductScorerBuilder::new(scorers()).with_compensation()),
+                0.625 * 0.88,
+            ),
+            (Box::new(MinScorerBuilder::new(scorers())), 0.5),
+            (Box::new(MaxScorerBuilder::new(scorers())), 0.8),
+            (
+                Box::new(WeightedSumScorerBuilder::new(
+                    scorers()
+                        .into_iter()
+                        .zip([2.0, 0.5])
+                        .map(|(s, w)| (w, s))
+                        .collect(),
+                )),
+                1.4,
+            ),
+        ];
+        for (builder, expected) in cases {
+            let found = score(&mut world, entity, builder.as_ref());
+            assert!(
+                (found - expected).abs() < 1e-5,
+                "{:?} should score {}. found: {}",
+                builder,
+                expected,
+                found
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_scorer_from_ron() {
+        let (mut world, entity) = test_world();
+        let builder: Box<dyn ScorerBuilder> = ron::from_str(
+            r#"{
+                "type": "ProductScorerBuilder",
+                "scorers": [
+                    {"type": "CurveScorerBuilder", "input": Component(type_path: "TestStats", field: "health"), "curve": Piecewise([(0.0, 1.0), (1.0, 0.0)])},
+                    {"type": "CurveScorerBuilder", "input": Blackboard("alert"), "curve": Constant(0.8)},
+                ],
+            }"#,
+        )
+        .expect("Failed to deserialize.");
+        let found = score(&mut world, entity, builder.as_ref());
+        assert!(
+            (found - 0.4).abs() < 1e-5,
+            "Scorer from RON should score. found: {}",
+            found
+        );
+    }
+}
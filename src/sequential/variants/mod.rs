@@ -7,6 +7,7 @@ use super::{Picker, PickerBuilder, ResultStrategy, ScoredSequence, Scorer, Score
 use crate as bevior_tree;
 use crate::node::prelude::*;
 
+pub mod reactive;
 pub mod sorted;
 
 #[cfg(feature = "random")]
@@ -14,8 +15,9 @@ pub mod random;
 
 pub mod prelude {
     pub use super::{
-        ForcedSequence, IdentityPickerBuilder, Selector, Sequence, SequentialAnd, SequentialOr,
-        random::prelude::*, score_uniform, sorted::prelude::*,
+        ConstantScorerBuilder, ForcedSequence, IdentityPickerBuilder, Selector, Sequence,
+        SequentialAnd, SequentialOr, random::prelude::*, reactive::prelude::*, score_uniform,
+        sorted::prelude::*,
     };
 }
 
@@ -43,8 +45,13 @@ pub fn score_uniform(nodes: Vec<Box<dyn Node>>) -> Vec<(Box<dyn Node>, Box<dyn S
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug)]
-struct ConstantScorerBuilder {
-    score: f32,
+pub struct ConstantScorerBuilder {
+    pub score: f32,
+}
+impl ConstantScorerBuilder {
+    pub fn new(score: f32) -> Self {
+        Self { score }
+    }
 }
 #[cfg_attr(feature = "serde", typetag::serde)]
 impl ScorerBuilder for ConstantScorerBuilder {
@@ -76,7 +83,7 @@ impl PickerBuilder for IdentityPickerBuilder {
 pub struct AndResultStrategy;
 #[cfg_attr(feature = "serde", typetag::serde)]
 impl ResultStrategy for AndResultStrategy {
-    fn construct(&self, results: Vec<Option<NodeResult>>) -> Option<NodeResult> {
+    fn construct(&self, results: &[Option<NodeResult>]) -> Option<NodeResult> {
         if results.contains(&Some(NodeResult::Failure)) {
             Some(NodeResult::Failure)
         } else if results.contains(&None) {
@@ -92,7 +99,7 @@ impl ResultStrategy for AndResultStrategy {
 pub struct OrResultStrategy;
 #[cfg_attr(feature = "serde", typetag::serde)]
 impl ResultStrategy for OrResultStrategy {
-    fn construct(&self, results: Vec<Option<NodeResult>>) -> Option<NodeResult> {
+    fn construct(&self, results: &[Option<NodeResult>]) -> Option<NodeResult> {
         if results.contains(&Some(NodeResult::Success)) {
             Some(NodeResult::Success)
         } else if results.contains(&None) {
@@ -108,7 +115,7 @@ impl ResultStrategy for OrResultStrategy {
 pub struct LastResultStrategy;
 #[cfg_attr(feature = "serde", typetag::serde)]
 impl ResultStrategy for LastResultStrategy {
-    fn construct(&self, results: Vec<Option<NodeResult>>) -> Option<NodeResult> {
+    fn construct(&self, results: &[Option<NodeResult>]) -> Option<NodeResult> {
         if results.contains(&None) {
             None
         } else {
@@ -125,8 +132,8 @@ impl ResultStrategy for LastResultStrategy {
 pub struct ForcedResultStrategy;
 #[cfg_attr(feature = "serde", typetag::serde)]
 impl ResultStrategy for ForcedResultStrategy {
-    fn construct(&self, results: Vec<Option<NodeResult>>) -> Option<NodeResult> {
-        results.into_iter().find_map(|r| r)
+    fn construct(&self, results: &[Option<NodeResult>]) -> Option<NodeResult> {
+        results.iter().find_map(|r| *r)
     }
 }
 
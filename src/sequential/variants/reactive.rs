@@ -0,0 +1,214 @@
+use bevy::ecs::{entity::Entity, world::World};
+
+use crate as bevior_tree;
+use crate::node::{VacantState, prelude::*};
+
+pub mod prelude {
+    pub use super::{ReactiveSelector, ReactiveSequence, ReactiveSequential};
+}
+
+/// Composite node that runs children in order until one of them returns `stop_on`,
+///  re-evaluating the children before the running one on every update.
+/// When one of them returns `stop_on` or starts running, the running child is force exited.
+///
+/// Children before the running one are begun again on every update,
+///  so conditions like [`crate::conditional::CheckIf`] fit them well.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+#[with_state(ReactiveSequentialState)]
+pub struct ReactiveSequential {
+    children: Vec<Box<dyn Node>>,
+    stop_on: NodeResult,
+}
+impl ReactiveSequential {
+    pub fn new(children: Vec<Box<dyn Node>>, stop_on: NodeResult) -> Self {
+        Self { children, stop_on }
+    }
+    /// Proceeds from the child with the status, beginning the following children.
+    fn proceed(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        mut index: usize,
+        mut status: NodeStatus,
+    ) -> NodeStatus {
+        loop {
+            match status {
+                NodeStatus::Beginning => panic!("Invalid child status."),
+                NodeStatus::Pending(child_state) => {
+                    return NodeStatus::Pending(Box::new(ReactiveSequentialState {
+                        index,
+                        child_state,
+                    }));
+                }
+                NodeStatus::Complete(result) if result == self.stop_on => {
+                    return NodeStatus::Complete(result);
+                }
+                NodeStatus::Complete(_) => {
+                    index += 1;
+                    let Some(child) = self.children.get(index) else {
+                        return NodeStatus::Complete(!self.stop_on);
+                    };
+                    status = child.begin(world, entity);
+                }
+            }
+        }
+    }
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl Node for ReactiveSequential {
+    fn begin(&self, world: &mut World, entity: Entity) -> NodeStatus {
+        let Some(child) = self.children.first() else {
+            return NodeStatus::Complete(!self.stop_on);
+        };
+        let status = child.begin(world, entity);
+        self.proceed(world, entity, 0, status)
+    }
+
+    fn resume(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        mut state: Box<dyn NodeState>,
+    ) -> NodeStatus {
+        let result = self.resume_in_place(world, entity, &mut state);
+        NodeStatus::from_resumed(result, state)
+    }
+
+    fn resume_in_place(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        state: &mut Box<dyn NodeState>,
+    ) -> Option<NodeResult> {
+        let inner = Self::downcast_mut(state).expect("Invalid state.");
+        let running = inner.index;
+        for (index, child) in self.children[..running].iter().enumerate() {
+            match child.begin(world, entity) {
+                NodeStatus::Complete(result) if result != self.stop_on => {}
+                status => {
+                    let child_state = VacantState::take(&mut inner.child_state);
+                    self.children[running].force_exit(world, entity, child_state);
+                    return self
+                        .proceed(world, entity, index, status)
+                        .into_resumed(state);
+                }
+            }
+        }
+        let result =
+            self.children[running].resume_in_place(world, entity, &mut inner.child_state)?;
+        self.proceed(world, entity, running, NodeStatus::Complete(result))
+            .into_resumed(state)
+    }
+
+    fn force_exit(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) {
+        let state = Self::downcast(state).expect("Invalid state.");
+        self.children[state.index].force_exit(world, entity, state.child_state);
+    }
+}
+
+/// State for [`ReactiveSequential`]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(NodeState, Debug)]
+struct ReactiveSequentialState {
+    index: usize,
+    child_state: Box<dyn NodeState>,
+}
+
+/// Node that runs children while their result is Failure,
+///  switching to the prior child as soon as it succeeds or starts running.
+#[delegate_node(delegate)]
+pub struct ReactiveSelector {
+    delegate: ReactiveSequential,
+}
+impl ReactiveSelector {
+    pub fn new(children: Vec<Box<dyn Node>>) -> Self {
+        Self {
+            delegate: ReactiveSequential::new(children, NodeResult::Success),
+        }
+    }
+}
+
+/// Node that runs children while their result is Success,
+///  aborting as soon as the prior child fails.
+#[delegate_node(delegate)]
+pub struct ReactiveSequence {
+    delegate: ReactiveSequential,
+}
+impl ReactiveSequence {
+    pub fn new(children: Vec<Box<dyn Node>>) -> Self {
+        Self {
+            delegate: ReactiveSequential::new(children, NodeResult::Failure),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tester_util::prelude::*;
+
+    #[test]
+    fn test_reactive_selector() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let selector = ReactiveSelector::new(vec![
+            Box::new(SequentialAnd::new(vec![
+                Box::new(CheckIf::new(TestMarkerExistsCondCheckerBuilder)),
+                Box::new(TesterTask1::new(10, NodeResult::Success)),
+            ])),
+            Box::new(TesterTask0::new(10, NodeResult::Success)),
+        ]);
+        let tree = BehaviorTree::from_node(
+            selector,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        app.update();
+        app.update(); // 0
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_some(),
+            "Lower priority child should run while the prior one fails."
+        );
+        app.world_mut().entity_mut(entity).insert(TestMarker);
+        app.update(); // 0, switch to 1
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_none()
+                && app.world().get::<TesterComponent<1>>(entity).is_some(),
+            "Prior child should preempt the running one."
+        );
+    }
+
+    #[test]
+    fn test_reactive_sequence() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let sequence = ReactiveSequence::new(vec![
+            Box::new(CheckIf::new(TestMarkerExistsCondCheckerBuilder)),
+            Box::new(TesterTask0::new(10, NodeResult::Success)),
+        ]);
+        let tree = BehaviorTree::from_node(
+            sequence,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn((tree, TestMarker)).id();
+        app.update();
+        app.update(); // 0
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_some(),
+            "Child should run while the prior one succeeds."
+        );
+        app.world_mut().entity_mut(entity).remove::<TestMarker>();
+        app.update(); // abort
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_none(),
+            "Running child should exit when the prior one fails."
+        );
+        let status = app.world().get::<TreeStatus>(entity).unwrap();
+        assert!(
+            status.result() == Some(NodeResult::Failure),
+            "ReactiveSequence should fail with the prior child. found: {:?}",
+            status
+        );
+    }
+}
@@ -0,0 +1,297 @@
+//! Node that runs another behavior tree asset as its child.
+
+use std::sync::Arc;
+
+use bevy::{
+    asset::{AssetServer, Assets, Handle, LoadState},
+    ecs::{entity::Entity, world::World},
+};
+
+use crate::BehaviorTreeRoot;
+use crate::node::prelude::*;
+
+pub mod prelude {
+    pub use super::SubTree;
+}
+
+/// Node that runs the root of another tree asset.
+/// Pending while the asset is loading, Failure if the asset is not available otherwise.
+///
+/// Serialized as the asset path, so the asset should be loaded from a path.
+/// [`crate::loader::BehaviorTreeLoader`] loads it as dependency, and rejects the trees including themselves.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+#[with_state(SubTreeState)]
+pub struct SubTree {
+    #[cfg_attr(feature = "serde", serde(with = "root_path"))]
+    root: Handle<BehaviorTreeRoot>,
+}
+impl SubTree {
+    pub fn new(root: Handle<BehaviorTreeRoot>) -> Self {
+        Self { root }
+    }
+    fn root_node(&self, world: &World) -> Option<Arc<dyn Node>> {
+        world
+            .get_resource::<Assets<BehaviorTreeRoot>>()?
+            .get(&self.root)
+            .map(|root| root.node.clone())
+    }
+    fn is_loading(&self, world: &World) -> bool {
+        world
+            .get_resource::<AssetServer>()
+            .is_some_and(|server| matches!(server.load_state(&self.root), LoadState::Loading))
+    }
+    fn wrap(node: Arc<dyn Node>, status: NodeStatus) -> NodeStatus {
+        match status {
+            NodeStatus::Pending(state) => {
+                NodeStatus::Pending(Box::new(SubTreeState::Running { node, state }))
+            }
+            _ => status,
+        }
+    }
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl Node for SubTree {
+    fn begin(&self, world: &mut World, entity: Entity) -> NodeStatus {
+        match self.root_node(world) {
+            Some(node) => Self::wrap(node.clone(), node.begin(world, entity)),
+            None if self.is_loading(world) => NodeStatus::Pending(Box::new(SubTreeState::Loading)),
+            None => NodeStatus::Complete(NodeResult::Failure),
+        }
+    }
+    fn resume(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        mut state: Box<dyn NodeState>,
+    ) -> NodeStatus {
+        let result = self.resume_in_place(world, entity, &mut state);
+        NodeStatus::from_resumed(result, state)
+    }
+    fn resume_in_place(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        state: &mut Box<dyn NodeState>,
+    ) -> Option<NodeResult> {
+        match Self::downcast_mut(state).expect("Invalid state.") {
+            SubTreeState::Loading => self.begin(world, entity).into_resumed(state),
+            SubTreeState::Running { node, state } => node.resume_in_place(world, entity, state),
+        }
+    }
+    fn force_exit(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) {
+        if let SubTreeState::Running { node, state } =
+            Self::downcast(state).expect("Invalid state.")
+        {
+            node.force_exit(world, entity, state);
+        }
+    }
+}
+
+/// State for [`SubTree`].
+/// Holds the root it began with, the state is only valid for it even if the asset is modified.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(NodeState, Debug)]
+enum SubTreeState {
+    Loading,
+    Running {
+        node: Arc<dyn Node>,
+        state: Box<dyn NodeState>,
+    },
+}
+
+#[cfg(feature = "serde")]
+mod root_path {
+    use bevy::asset::{AssetPath, Handle};
+    use serde::{Deserialize, Deserializer, Serializer, de::Error as _, ser::Error as _};
+
+    use crate::BehaviorTreeRoot;
+
+    pub fn serialize<S: Serializer>(
+        root: &Handle<BehaviorTreeRoot>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let path = root
+            .path()
+            .ok_or_else(|| S::Error::custom("SubTree root is not loaded from path."))?;
+        serializer.serialize_str(&path.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Handle<BehaviorTreeRoot>, D::Error> {
+        let path = AssetPath::from(String::deserialize(deserializer)?);
+        crate::loader::subtree_handle(path).ok_or_else(|| {
+            D::Error::custom("SubTree is only deserializable by BehaviorTreeLoader.")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tester_util::prelude::*;
+
+    #[test]
+    fn test_subtree() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let root = app
+            .world_mut()
+            .resource_mut::<Assets<BehaviorTreeRoot>>()
+            .add(BehaviorTreeRoot {
+                node: Arc::new(TesterTask1::new(1, NodeResult::Failure)),
+            });
+        let sequence = SequentialAnd::new(vec![
+            Box::new(TesterTask0::new(1, NodeResult::Success)),
+            Box::new(SubTree::new(root)),
+            Box::new(TesterTask2::new(1, NodeResult::Success)),
+        ]);
+        let tree = BehaviorTree::from_node(
+            sequence,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        app.update();
+        app.update(); // 0
+        app.update(); // 1, sequence complete with Failure
+        app.update(); // nop
+        let expected = TestLog {
+            log: vec![
+                TestLogEntry {
+                    task_id: 0,
+                    updated_count: 0,
+                    frame: 1,
+                },
+                TestLogEntry {
+                    task_id: 1,
+                    updated_count: 0,
+                    frame: 2,
+                },
+            ],
+        };
+        let found = app.world().get_resource::<TestLog>().unwrap();
+        assert!(found == &expected, "Result mismatch. found: {:?}", found);
+        let status = app.world().get::<TreeStatus>(entity).unwrap();
+        assert!(
+            status.result() == Some(NodeResult::Failure),
+            "Result of the sub-tree should be delegated. found: {:?}",
+            status
+        );
+    }
+
+    #[test]
+    fn test_subtree_force_exit() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let root = app
+            .world_mut()
+            .resource_mut::<Assets<BehaviorTreeRoot>>()
+            .add(BehaviorTreeRoot {
+                node: Arc::new(TesterTask0::new(5, NodeResult::Success)),
+            });
+        let tree = BehaviorTree::from_node(
+            SubTree::new(root),
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        app.update();
+        app.update();
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_some(),
+            "Task in the sub-tree should be running."
+        );
+        BehaviorTree::abort(app.world_mut(), entity);
+        app.update();
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_none(),
+            "Task in the sub-tree should exit on abort."
+        );
+    }
+
+    #[test]
+    fn test_subtree_removed_root() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let root = app
+            .world_mut()
+            .resource_mut::<Assets<BehaviorTreeRoot>>()
+            .add(BehaviorTreeRoot {
+                node: Arc::new(TesterTask0::new(1, NodeResult::Success)),
+            });
+        app.world_mut()
+            .resource_mut::<Assets<BehaviorTreeRoot>>()
+            .remove(&root);
+        let tree = BehaviorTree::from_node(
+            SubTree::new(root),
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        app.update();
+        app.update();
+        let status = app.world().get::<TreeStatus>(entity).unwrap();
+        assert!(
+            status.result() == Some(NodeResult::Failure),
+            "Sub-tree without the root should fail. found: {:?}",
+            status
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_load_subtree() {
+        write_test_file(
+            "subtree_child.bt.ron",
+            r#"(node: {"type": "TesterTask1", "task": (definition: {"type": "TesterTaskDef1", "count": 1, "result": Success})})"#,
+        );
+        write_test_file(
+            "subtree_parent.bt.ron",
+            r#"(node: {"type": "SubTree", "root": "subtree_child.bt.ron"})"#,
+        );
+        let (app, entity) = load_test_file("subtree_parent.bt.ron");
+        let status = app.world().get::<TreeStatus>(entity);
+        assert!(
+            status.is_some_and(|status| status.result() == Some(NodeResult::Success)),
+            "Sub-tree loaded from path should run. found: {:?}",
+            status
+        );
+        let root = app
+            .world()
+            .get::<BehaviorTree>(entity)
+            .unwrap()
+            .root
+            .clone();
+        let subtree = app
+            .world()
+            .resource::<Assets<BehaviorTreeRoot>>()
+            .get(&root)
+            .map(|root| ron::to_string(root).expect("Failed to serialize."));
+        assert!(
+            subtree
+                .as_ref()
+                .is_some_and(|ron| ron.contains("\"subtree_child.bt.ron\"")),
+            "Sub-tree should be serialized as the path. found: {:?}",
+            subtree
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_subtree_cycle() {
+        write_test_file(
+            "subtree_cycle_0.bt.ron",
+            r#"(node: {"type": "SubTree", "root": "subtree_cycle_1.bt.ron"})"#,
+        );
+        write_test_file(
+            "subtree_cycle_1.bt.ron",
+            r#"(node: {"type": "SubTree", "root": "subtree_cycle_0.bt.ron"})"#,
+        );
+        let (app, entity) = load_test_file("subtree_cycle_0.bt.ron");
+        let error = app.world().get::<TreeLoadError>(entity);
+        assert!(
+            error.is_some_and(|error| error.error.to_string().contains("cycle")),
+            "Including cycle should be reported. found: {:?}",
+            error
+        );
+    }
+}
@@ -0,0 +1,262 @@
+//! Node that runs a future on [`AsyncComputeTaskPool`].
+
+use std::{fmt::Debug, future::Future, pin::Pin, sync::Mutex};
+
+use bevy::{
+    ecs::{
+        bundle::Bundle,
+        entity::Entity,
+        system::{In, System},
+        world::{EntityWorldMut, World},
+    },
+    tasks::{AsyncComputeTaskPool, Task, futures::check_ready},
+};
+
+use crate::node::prelude::*;
+
+pub mod prelude {
+    pub use super::{
+        AsyncTask, AsyncTaskDefinition, AsyncTaskFuture, AsyncTaskOutput, AsyncTaskSpawner,
+    };
+}
+
+type OutputWriter = Box<dyn FnOnce(&mut EntityWorldMut) + Send>;
+
+/// Result of the future, with the output to write on the entity.
+pub struct AsyncTaskOutput {
+    result: NodeResult,
+    write: Option<OutputWriter>,
+}
+impl AsyncTaskOutput {
+    pub fn new(result: NodeResult) -> Self {
+        Self {
+            result,
+            write: None,
+        }
+    }
+    /// Inserts the bundle to the entity on completion.
+    pub fn with_output(mut self, output: impl Bundle) -> Self {
+        self.write = Some(Box::new(move |entity| {
+            entity.insert(output);
+        }));
+        self
+    }
+}
+
+pub type AsyncTaskFuture = Pin<Box<dyn Future<Output = AsyncTaskOutput> + Send>>;
+
+/// System that reads the inputs from the world and makes the future.
+pub type AsyncTaskSpawner = dyn System<In = In<Entity>, Out = AsyncTaskFuture>;
+
+#[cfg_attr(feature = "serde", typetag::serde(tag = "type"))]
+pub trait AsyncTaskDefinition: 'static + Debug + Send + Sync {
+    fn build_spawner(&self) -> Box<AsyncTaskSpawner>;
+}
+
+/// Node that runs the future off the main thread, and completes with its result.
+/// Spawns the future on begin and polls it on resume.
+/// Force exit cancels the future.
+///
+/// The running future is not serialized, the deserialized state completes with Failure.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+#[with_state(AsyncTaskState)]
+pub struct AsyncTask {
+    definition: Box<dyn AsyncTaskDefinition>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    spawner: Mutex<Option<Box<AsyncTaskSpawner>>>,
+}
+impl AsyncTask {
+    pub fn new(definition: impl AsyncTaskDefinition) -> Self {
+        Self {
+            definition: Box::new(definition),
+            spawner: Mutex::new(None),
+        }
+    }
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl Node for AsyncTask {
+    fn begin(&self, world: &mut World, entity: Entity) -> NodeStatus {
+        let future = {
+            let mut spawner = self.spawner.lock().expect("Failed to lock.");
+            let spawner = spawner.get_or_insert_with(|| {
+                let mut spawner = self.definition.build_spawner();
+                spawner.initialize(world);
+                spawner
+            });
+            spawner
+                .run(entity, world)
+                .expect("Failed to run spawner system.")
+        };
+        let task = AsyncComputeTaskPool::get().spawn(future);
+        self.resume(world, entity, Box::new(AsyncTaskState { task: Some(task) }))
+    }
+
+    fn resume(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        mut state: Box<dyn NodeState>,
+    ) -> NodeStatus {
+        let result = self.resume_in_place(world, entity, &mut state);
+        NodeStatus::from_resumed(result, state)
+    }
+
+    fn resume_in_place(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        state: &mut Box<dyn NodeState>,
+    ) -> Option<NodeResult> {
+        let state = Self::downcast_mut(state).expect("Invalid state.");
+        let Some(task) = state.task.as_mut() else {
+            return Some(NodeResult::Failure);
+        };
+        let output = check_ready(task)?;
+        if let Some(write) = output.write
+            && let Ok(mut entity) = world.get_entity_mut(entity)
+        {
+            write(&mut entity);
+        }
+        Some(output.result)
+    }
+
+    fn force_exit(&self, _world: &mut World, _entity: Entity, state: Box<dyn NodeState>) {
+        // Dropping the task cancels the future.
+        let _state = Self::downcast(state).expect("Invalid state.");
+    }
+}
+
+/// State for [`AsyncTask`]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(NodeState, Debug)]
+struct AsyncTaskState {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    task: Option<Task<AsyncTaskOutput>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    };
+
+    use super::*;
+    use crate::tester_util::prelude::*;
+
+    #[derive(Component)]
+    struct TestInput(u32);
+
+    #[derive(Component, Debug, PartialEq)]
+    struct TestOutput(u32);
+
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[derive(Debug)]
+    struct DoubleTask;
+    #[cfg_attr(feature = "serde", typetag::serde)]
+    impl AsyncTaskDefinition for DoubleTask {
+        fn build_spawner(&self) -> Box<AsyncTaskSpawner> {
+            Box::new(IntoSystem::into_system(
+                |In(entity): In<Entity>, query: Query<&TestInput>| -> AsyncTaskFuture {
+                    let input = query.get(entity).expect("TestInput not found").0;
+                    Box::pin(async move {
+                        AsyncTaskOutput::new(NodeResult::Success).with_output(TestOutput(input * 2))
+                    })
+                },
+            ))
+        }
+    }
+
+    /// Sets the flag when dropped with the future.
+    #[derive(Debug)]
+    struct DropFlag(Arc<AtomicBool>);
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[derive(Resource)]
+    struct TestDropped(Arc<AtomicBool>);
+
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[derive(Debug)]
+    struct PendingTask;
+    #[cfg_attr(feature = "serde", typetag::serde)]
+    impl AsyncTaskDefinition for PendingTask {
+        fn build_spawner(&self) -> Box<AsyncTaskSpawner> {
+            Box::new(IntoSystem::into_system(
+                |In(_entity): In<Entity>, dropped: Res<TestDropped>| -> AsyncTaskFuture {
+                    let flag = DropFlag(dropped.0.clone());
+                    Box::pin(async move {
+                        let _flag = flag;
+                        std::future::pending::<AsyncTaskOutput>().await
+                    })
+                },
+            ))
+        }
+    }
+
+    /// Updates until the condition holds, the future runs on other threads.
+    fn update_until(app: &mut App, condition: impl Fn(&App) -> bool) -> bool {
+        for _ in 0..1000 {
+            if condition(app) {
+                return true;
+            }
+            app.update();
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        condition(app)
+    }
+
+    #[test]
+    fn test_async_task() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()));
+        let tree = BehaviorTree::from_node(
+            AsyncTask::new(DoubleTask),
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn((tree, TestInput(21))).id();
+        let completed = update_until(&mut app, |app| {
+            app.world()
+                .get::<TreeStatus>(entity)
+                .is_some_and(|status| status.result().is_some())
+        });
+        let status = app.world().get::<TreeStatus>(entity).unwrap();
+        assert!(
+            completed && status.result() == Some(NodeResult::Success),
+            "AsyncTask should complete with the result of the future. found: {:?}",
+            status
+        );
+        let output = app.world().get::<TestOutput>(entity);
+        assert!(
+            output == Some(&TestOutput(42)),
+            "AsyncTask should write the output. found: {:?}",
+            output
+        );
+    }
+
+    #[test]
+    fn test_async_task_force_exit() {
+        let mut app = App::new();
+        let dropped = Arc::new(AtomicBool::new(false));
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()))
+            .insert_resource(TestDropped(dropped.clone()));
+        let tree = BehaviorTree::from_node(
+            AsyncTask::new(PendingTask),
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        app.update();
+        app.update();
+        assert!(
+            !dropped.load(Ordering::SeqCst),
+            "Future should be running before force exit."
+        );
+        BehaviorTree::abort(app.world_mut(), entity);
+        let cancelled = update_until(&mut app, |_| dropped.load(Ordering::SeqCst));
+        assert!(cancelled, "Force exit should cancel the future.");
+    }
+}
@@ -5,16 +5,23 @@ use std::{fmt::Debug, sync::Mutex, vec};
 use bevy::ecs::{
     bundle::Bundle,
     entity::Entity,
-    system::{Commands, In, IntoSystem, System},
+    message::{Message, MessageReader},
+    observer::On,
+    resource::Resource,
+    system::{Commands, In, IntoSystem, ResMut, System},
     world::World,
 };
+use bevy::platform::collections::{HashMap, hash_map::Entry};
+use bevy::prelude::EntityEvent;
 
 use crate::node::prelude::*;
 
+pub mod async_task;
+
 pub mod prelude {
     pub use super::{
-        TaskBridge, TaskChecker, TaskDefinition, TaskEvent, TaskEventListener, TaskStatus,
-        insert_while_running,
+        TaskBridge, TaskChecker, TaskCompletion, TaskDefinition, TaskEvent, TaskEventListener,
+        TaskFinished, TaskStatus, async_task::prelude::*, insert_while_running,
     };
 }
 
@@ -45,10 +52,83 @@ pub type TaskChecker = dyn System<In = In<Entity>, Out = TaskStatus>;
 
 pub type TaskEventListener = dyn System<In = In<Entity>, Out = ()>;
 
+/// How [`TaskBridge`] knows the completion of the task.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TaskCompletion {
+    /// Runs the checker on every update.
+    #[default]
+    Poll,
+    /// Runs the checker only on begin, then waits for [`TaskFinished`] on the entity.
+    Event,
+}
+
 #[cfg_attr(feature = "serde", typetag::serde(tag = "type"))]
 pub trait TaskDefinition: 'static + Debug + Send + Sync {
     fn build_checker(&self) -> Box<TaskChecker>;
     fn build_event_listeners(&self) -> Vec<(TaskEvent, Box<TaskEventListener>)>;
+    fn completion(&self) -> TaskCompletion {
+        TaskCompletion::Poll
+    }
+}
+
+/// Completes the task waiting with [`TaskCompletion::Event`] on the entity.
+/// Either trigger it or write it as message.
+///
+/// One event completes one waiting task, the first one to be updated if the entity has many.
+/// Events arrived before the task began are discarded.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(EntityEvent, Message, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TaskFinished {
+    pub entity: Entity,
+    pub result: NodeResult,
+}
+
+/// Results of [`TaskFinished`] until the waiting tasks take them.
+/// Only the entities with the waiting tasks are kept, so that the tasks not waiting skip it.
+#[derive(Resource, Debug, Default)]
+pub(crate) struct FinishedTasks {
+    /// Count of the tasks waiting on each entity.
+    waiting: HashMap<Entity, usize>,
+    results: HashMap<Entity, NodeResult>,
+}
+impl FinishedTasks {
+    fn finish(&mut self, event: &TaskFinished) {
+        if self.waiting.contains_key(&event.entity) {
+            self.results.insert(event.entity, event.result);
+        }
+    }
+    fn wait(&mut self, entity: Entity) {
+        *self.waiting.entry(entity).or_default() += 1;
+    }
+    fn stop_waiting(&mut self, entity: Entity) {
+        if let Entry::Occupied(mut count) = self.waiting.entry(entity) {
+            *count.get_mut() -= 1;
+            if *count.get() == 0 {
+                count.remove();
+                self.results.remove(&entity);
+            }
+        }
+    }
+    fn take(&mut self, entity: Entity) -> Option<NodeResult> {
+        if self.results.is_empty() {
+            return None;
+        }
+        self.results.remove(&entity)
+    }
+}
+
+pub(crate) fn observe_task_finished(event: On<TaskFinished>, mut finished: ResMut<FinishedTasks>) {
+    finished.finish(&event);
+}
+
+pub(crate) fn read_task_finished(
+    mut reader: MessageReader<TaskFinished>,
+    mut finished: ResMut<FinishedTasks>,
+) {
+    for event in reader.read() {
+        finished.finish(event);
+    }
 }
 
 /// Event listeners that add the bundle on entering node then remove it on exiting.
@@ -68,7 +148,8 @@ pub fn insert_while_running<T: Bundle + 'static + Clone>(
             TaskEvent::Exit,
             Box::new(IntoSystem::into_system(
                 |In(entity), mut commands: Commands| {
-                    commands.entity(entity).remove::<T>();
+                    // The entity may be despawned already when the tree is aborted.
+                    commands.entity(entity).try_remove::<T>();
                 },
             )),
         ),
@@ -120,6 +201,15 @@ impl TaskBridge {
             .expect("Failed to run checker system.")
     }
 
+    fn complete(&self, world: &mut World, entity: Entity, result: NodeResult) -> NodeStatus {
+        match result {
+            NodeResult::Success => self.trigger_event(world, entity, TaskEvent::Success),
+            NodeResult::Failure => self.trigger_event(world, entity, TaskEvent::Failure),
+        }
+        self.trigger_event(world, entity, TaskEvent::Exit);
+        NodeStatus::Complete(result)
+    }
+
     fn trigger_event(&self, world: &mut World, entity: Entity, event: TaskEvent) {
         let mut listeners = self.event_listeners.lock().expect("Failed to lock.");
         // Initialize event listeners if not yet.
@@ -143,25 +233,162 @@ impl TaskBridge {
 impl Node for TaskBridge {
     fn begin(&self, world: &mut World, entity: Entity) -> NodeStatus {
         self.trigger_event(world, entity, TaskEvent::Enter);
-        self.resume(world, entity, Box::new(TaskState))
+        match self.check(world, entity) {
+            TaskStatus::Running => {
+                if self.definition.completion() == TaskCompletion::Event {
+                    world.get_resource_or_init::<FinishedTasks>().wait(entity);
+                }
+                NodeStatus::Pending(Box::new(TaskState))
+            }
+            TaskStatus::Complete(result) => self.complete(world, entity, result),
+        }
     }
     fn resume(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) -> NodeStatus {
         let state = Self::downcast(state).expect("Invalid state.");
-        let status = self.check(world, entity);
-        match status {
-            TaskStatus::Running => NodeStatus::Pending(Box::new(state)),
-            TaskStatus::Complete(result) => {
-                match result {
-                    NodeResult::Success => self.trigger_event(world, entity, TaskEvent::Success),
-                    NodeResult::Failure => self.trigger_event(world, entity, TaskEvent::Failure),
+        let status = match self.definition.completion() {
+            TaskCompletion::Poll => self.check(world, entity),
+            TaskCompletion::Event => {
+                let Some(mut finished) = world.get_resource_mut::<FinishedTasks>() else {
+                    return NodeStatus::Pending(Box::new(state));
+                };
+                match finished.take(entity) {
+                    Some(result) => {
+                        finished.stop_waiting(entity);
+                        TaskStatus::Complete(result)
+                    }
+                    None => TaskStatus::Running,
                 }
-                self.trigger_event(world, entity, TaskEvent::Exit);
-                NodeStatus::Complete(result)
             }
+        };
+        match status {
+            TaskStatus::Running => NodeStatus::Pending(Box::new(state)),
+            TaskStatus::Complete(result) => self.complete(world, entity, result),
         }
     }
     fn force_exit(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) {
         let _state = Self::downcast(state).expect("Invalid state.");
+        if self.definition.completion() == TaskCompletion::Event
+            && let Some(mut finished) = world.get_resource_mut::<FinishedTasks>()
+        {
+            finished.stop_waiting(entity);
+        }
         self.trigger_event(world, entity, TaskEvent::Exit);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tester_util::prelude::*;
+
+    #[derive(Resource, Default)]
+    struct CheckCount(u32);
+
+    /// Task completes on [`TaskFinished`], counts the checks on the resource.
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[derive(Debug)]
+    struct EventTaskDefinition;
+    #[cfg_attr(feature = "serde", typetag::serde)]
+    impl TaskDefinition for EventTaskDefinition {
+        fn build_checker(&self) -> Box<TaskChecker> {
+            Box::new(IntoSystem::into_system(
+                |In(_): In<Entity>, mut count: ResMut<CheckCount>| {
+                    count.0 += 1;
+                    TaskStatus::Running
+                },
+            ))
+        }
+        fn build_event_listeners(&self) -> Vec<(TaskEvent, Box<TaskEventListener>)> {
+            vec![]
+        }
+        fn completion(&self) -> TaskCompletion {
+            TaskCompletion::Event
+        }
+    }
+
+    fn spawn_event_task(app: &mut App) -> Entity {
+        let tree = BehaviorTree::from_node(
+            TaskBridge::new(Box::new(EventTaskDefinition)),
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        app.world_mut().spawn(tree).id()
+    }
+
+    fn assert_result(app: &App, entity: Entity, expected: Option<NodeResult>) {
+        let status = app.world().get::<TreeStatus>(entity).unwrap();
+        assert!(
+            status.result() == expected,
+            "Task should complete on the event. expected: {:?}, found: {:?}",
+            expected,
+            status
+        );
+    }
+
+    #[test]
+    fn test_complete_on_triggered_event() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()))
+            .init_resource::<CheckCount>();
+        // Arrived before the task began, discarded.
+        let entity = spawn_event_task(&mut app);
+        app.world_mut().trigger(TaskFinished {
+            entity,
+            result: NodeResult::Success,
+        });
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_result(&app, entity, None);
+        assert!(
+            app.world().resource::<CheckCount>().0 == 1,
+            "Checker should run only on begin."
+        );
+        app.world_mut().trigger(TaskFinished {
+            entity,
+            result: NodeResult::Failure,
+        });
+        app.update();
+        assert_result(&app, entity, Some(NodeResult::Failure));
+    }
+
+    #[test]
+    fn test_complete_on_message() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()))
+            .init_resource::<CheckCount>();
+        let entity = spawn_event_task(&mut app);
+        app.update();
+        app.update();
+        assert_result(&app, entity, None);
+        app.world_mut().write_message(TaskFinished {
+            entity,
+            result: NodeResult::Success,
+        });
+        app.update();
+        assert_result(&app, entity, Some(NodeResult::Success));
+    }
+
+    #[test]
+    fn test_event_dropped_on_restart() {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()))
+            .init_resource::<CheckCount>();
+        let entity = spawn_event_task(&mut app);
+        app.update();
+        app.world_mut().trigger(TaskFinished {
+            entity,
+            result: NodeResult::Success,
+        });
+        BehaviorTree::restart(app.world_mut(), entity);
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_result(&app, entity, None);
+        let finished = app.world().resource::<FinishedTasks>();
+        assert!(
+            finished.results.is_empty() && finished.waiting.get(&entity) == Some(&1),
+            "Result for the exited task should be dropped. found: {:?}",
+            finished
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     BehaviorTree, BehaviorTreePlugin, BehaviorTreeRoot,
+    conditional::{CondChecker, CondCheckerBuilder},
     node::prelude::*,
     task::{
         TaskBridge, TaskChecker, TaskDefinition, TaskEvent, TaskEventListener, TaskStatus,
@@ -12,8 +13,11 @@ use bevy::prelude::*;
 
 pub mod prelude {
     pub use super::{
-        TestLog, TestLogEntry, TesterPlugin, TesterTask0, TesterTask1, TesterTask2, TesterTask3,
+        TestLog, TestLogEntry, TestMarker, TestMarkerExistsCondCheckerBuilder, TesterComponent,
+        TesterPlugin, TesterTask0, TesterTask1, TesterTask2, TesterTask3, log_frames,
     };
+    #[cfg(feature = "serde")]
+    pub use super::{load_test_file, load_test_file_with, write_test_file};
     pub use crate::prelude::*;
     pub use bevy::prelude::*;
 }
@@ -32,17 +36,45 @@ impl Plugin for TesterPlugin {
             .add_systems(Update, update::<7>)
             .init_resource::<TestLog>();
         #[cfg(feature = "serde")]
-        {
-            let test_asset_dir = std::path::Path::new("target").join("test_assets");
-            app.add_plugins((
-                AssetPlugin {
-                    file_path: test_asset_dir.to_string_lossy().to_string(),
-                    ..default()
-                },
-                bevy_common_assets::ron::RonAssetPlugin::<BehaviorTreeRoot>::new(&["ron"]),
-            ));
-        }
+        app.add_plugins(AssetPlugin {
+            file_path: test_asset_dir().to_string_lossy().to_string(),
+            ..default()
+        });
+    }
+}
+
+#[cfg(feature = "serde")]
+fn test_asset_dir() -> std::path::PathBuf {
+    std::path::Path::new("target").join("test_assets")
+}
+
+/// Writes the file into the asset directory of [`TesterPlugin`].
+#[cfg(feature = "serde")]
+pub fn write_test_file(file_name: &str, content: &str) {
+    let test_asset_dir = test_asset_dir();
+    std::fs::create_dir_all(&test_asset_dir).expect("Failed to create test directory");
+    std::fs::write(test_asset_dir.join(file_name), content).expect("Failed to write test asset");
+}
+
+/// App with the entity loading the tree from the path, updated until the load settles.
+#[cfg(feature = "serde")]
+pub fn load_test_file(path: &str) -> (App, Entity) {
+    load_test_file_with(path, BehaviorTreePlugin::default())
+}
+
+/// [`load_test_file`] with the given plugin.
+#[cfg(feature = "serde")]
+pub fn load_test_file_with(path: &str, plugin: BehaviorTreePlugin) -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins((TesterPlugin, plugin));
+    let source = crate::BehaviorTreeSource {
+        path: path.to_string(),
+    };
+    let entity = app.world_mut().spawn(source).id();
+    for _ in 0..50 {
+        app.update();
     }
+    (app, entity)
 }
 
 #[derive(Clone, Debug)]
@@ -160,6 +192,34 @@ fn update<const ID: i32>(
     }
 }
 
+/// Frames of the log entries, in order.
+pub fn log_frames(app: &App) -> Vec<u32> {
+    app.world()
+        .resource::<TestLog>()
+        .log
+        .iter()
+        .map(|entry| entry.frame)
+        .collect()
+}
+
+#[derive(Component)]
+pub struct TestMarker;
+
+/// Checks whether the entity has [`TestMarker`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+pub struct TestMarkerExistsCondCheckerBuilder;
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl CondCheckerBuilder for TestMarkerExistsCondCheckerBuilder {
+    fn build(&self) -> Box<CondChecker> {
+        Box::new(IntoSystem::into_system(
+            |In(entity): In<Entity>, world: &World| -> bool {
+                world.entity(entity).contains::<TestMarker>()
+            },
+        ))
+    }
+}
+
 #[test]
 fn test_enter_tester_task() {
     let mut app = App::new();
@@ -0,0 +1,824 @@
+//! Nodes that depend on the time.
+
+use std::{collections::hash_map::RandomState, hash::BuildHasher, time::Duration};
+
+use bevy::{
+    ecs::{component::Component, entity::Entity, resource::Resource, system::ResMut, world::World},
+    platform::collections::HashMap,
+    time::{Fixed, Real, Time, Virtual},
+};
+
+use crate::node::{VacantState, prelude::*};
+
+pub mod prelude {
+    pub use super::{Cooldown, CooldownMemory, Period, TimeSource, Timeout, Wait, WaitDuration};
+}
+
+/// Count of the updates of the trees, for frame-based [`Period`] outside of the node states.
+#[derive(Resource, Debug, Default, Clone, Copy)]
+pub(crate) struct UpdateCount(u32);
+
+pub(crate) fn count_updates(mut count: ResMut<UpdateCount>) {
+    count.0 = count.0.wrapping_add(1);
+}
+
+/// Clock that the nodes read.
+/// [`TimeSource::Virtual`] stops while the game is paused.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TimeSource {
+    #[default]
+    Virtual,
+    Real,
+    Fixed,
+}
+impl TimeSource {
+    /// Elapsed time of the clock, zero if the clock resource is missing.
+    pub fn elapsed(&self, world: &World) -> Duration {
+        match self {
+            TimeSource::Virtual => world.get_resource::<Time<Virtual>>().map(Time::elapsed),
+            TimeSource::Real => world.get_resource::<Time<Real>>().map(Time::elapsed),
+            TimeSource::Fixed => world.get_resource::<Time<Fixed>>().map(Time::elapsed),
+        }
+        .unwrap_or_default()
+    }
+}
+
+/// Length of time, or count of the updates.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    Time(Duration),
+    /// Counts the updates of the node, not including the begin.
+    Frames(u32),
+}
+
+/// Measures the time and the updates since the begin of the node.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Stopwatch {
+    elapsed: Duration,
+    frames: u32,
+    /// Elapsed time of the clock on the last update.
+    last_seen: Duration,
+}
+impl Stopwatch {
+    pub(crate) fn start(time: TimeSource, world: &World) -> Self {
+        Self {
+            elapsed: Duration::ZERO,
+            frames: 0,
+            last_seen: time.elapsed(world),
+        }
+    }
+    pub(crate) fn tick(&mut self, time: TimeSource, world: &World) {
+        let now = time.elapsed(world);
+        self.elapsed += now.saturating_sub(self.last_seen);
+        self.last_seen = now;
+        self.frames += 1;
+    }
+    pub(crate) fn reached(&self, period: Period) -> bool {
+        match period {
+            Period::Time(duration) => duration <= self.elapsed,
+            Period::Frames(frames) => frames <= self.frames,
+        }
+    }
+}
+
+/// Point of time that [`Cooldown`] remembers.
+#[derive(Debug, Clone, Copy)]
+struct Timestamp {
+    time: Duration,
+    update: u32,
+}
+impl Timestamp {
+    fn now(time: TimeSource, world: &World) -> Self {
+        Self {
+            time: time.elapsed(world),
+            update: world
+                .get_resource::<UpdateCount>()
+                .copied()
+                .unwrap_or_default()
+                .0,
+        }
+    }
+    fn passed(&self, since: &Timestamp, period: Period) -> bool {
+        match period {
+            Period::Time(duration) => duration <= self.time.saturating_sub(since.time),
+            Period::Frames(frames) => frames <= self.update.wrapping_sub(since.update),
+        }
+    }
+}
+
+/// How long [`Wait`] waits.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitDuration {
+    Fixed(Duration),
+    /// Picked uniformly from the range on every begin.
+    /// The bounds are swapped if `min` is greater than `max`.
+    #[cfg(feature = "random")]
+    Random {
+        min: Duration,
+        max: Duration,
+    },
+}
+impl WaitDuration {
+    fn pick(&self) -> Duration {
+        match self {
+            WaitDuration::Fixed(duration) => *duration,
+            #[cfg(feature = "random")]
+            WaitDuration::Random { min, max } => {
+                use rand::{distr::Uniform, prelude::Distribution};
+                let (min, max) = if min <= max { (min, max) } else { (max, min) };
+                let dist = Uniform::new_inclusive(min.as_secs_f64(), max.as_secs_f64())
+                    .expect("Failed to init uniform distribution.");
+                Duration::from_secs_f64(dist.sample(&mut rand::rng()))
+            }
+        }
+    }
+}
+
+/// Node that waits for the duration, then returns Success.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+#[with_state(WaitState)]
+pub struct Wait {
+    duration: WaitDuration,
+    time: TimeSource,
+}
+impl Wait {
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration: WaitDuration::Fixed(duration),
+            time: TimeSource::default(),
+        }
+    }
+    /// Waits for the duration picked from the range.
+    #[cfg(feature = "random")]
+    pub fn random(min: Duration, max: Duration) -> Self {
+        Self {
+            duration: WaitDuration::Random { min, max },
+            time: TimeSource::default(),
+        }
+    }
+    /// Reads the given clock rather than default [`TimeSource::Virtual`].
+    pub fn with_time(mut self, time: TimeSource) -> Self {
+        self.time = time;
+        self
+    }
+    fn check(state: &WaitState) -> Option<NodeResult> {
+        state
+            .stopwatch
+            .reached(Period::Time(state.duration))
+            .then_some(NodeResult::Success)
+    }
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl Node for Wait {
+    fn begin(&self, world: &mut World, _entity: Entity) -> NodeStatus {
+        let state = WaitState {
+            duration: self.duration.pick(),
+            stopwatch: Stopwatch::start(self.time, world),
+        };
+        match Self::check(&state) {
+            Some(result) => NodeStatus::Complete(result),
+            None => NodeStatus::Pending(Box::new(state)),
+        }
+    }
+    fn resume(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        mut state: Box<dyn NodeState>,
+    ) -> NodeStatus {
+        let result = self.resume_in_place(world, entity, &mut state);
+        NodeStatus::from_resumed(result, state)
+    }
+    fn resume_in_place(
+        &self,
+        world: &mut World,
+        _entity: Entity,
+        state: &mut Box<dyn NodeState>,
+    ) -> Option<NodeResult> {
+        let state = Self::downcast_mut(state).expect("Invalid state.");
+        state.stopwatch.tick(self.time, world);
+        Self::check(state)
+    }
+    fn force_exit(&self, _world: &mut World, _entity: Entity, _state: Box<dyn NodeState>) {}
+}
+
+/// State for [`Wait`]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(NodeState, Debug)]
+struct WaitState {
+    duration: Duration,
+    stopwatch: Stopwatch,
+}
+
+/// Node that force exits the child if it runs over the limit, then returns the result.
+/// The result is Failure unless configured with [`Timeout::with_result`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+#[with_state(TimeoutState)]
+pub struct Timeout {
+    child: Box<dyn Node>,
+    limit: Period,
+    time: TimeSource,
+    result: NodeResult,
+}
+impl Timeout {
+    pub fn new(child: impl Node, limit: Period) -> Self {
+        Self {
+            child: Box::new(child),
+            limit,
+            time: TimeSource::default(),
+            result: NodeResult::Failure,
+        }
+    }
+    /// Reads the given clock rather than default [`TimeSource::Virtual`].
+    pub fn with_time(mut self, time: TimeSource) -> Self {
+        self.time = time;
+        self
+    }
+    /// Completes with the given result on timeout.
+    pub fn with_result(mut self, result: NodeResult) -> Self {
+        self.result = result;
+        self
+    }
+    fn wrap(stopwatch: Stopwatch, child_status: NodeStatus) -> NodeStatus {
+        match child_status {
+            NodeStatus::Pending(child_state) => NodeStatus::Pending(Box::new(TimeoutState {
+                stopwatch,
+                child_state,
+            })),
+            _ => child_status,
+        }
+    }
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl Node for Timeout {
+    fn begin(&self, world: &mut World, entity: Entity) -> NodeStatus {
+        let stopwatch = Stopwatch::start(self.time, world);
+        Self::wrap(stopwatch, self.child.begin(world, entity))
+    }
+    fn resume(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        mut state: Box<dyn NodeState>,
+    ) -> NodeStatus {
+        let result = self.resume_in_place(world, entity, &mut state);
+        NodeStatus::from_resumed(result, state)
+    }
+    fn resume_in_place(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        state: &mut Box<dyn NodeState>,
+    ) -> Option<NodeResult> {
+        let state = Self::downcast_mut(state).expect("Invalid state.");
+        state.stopwatch.tick(self.time, world);
+        if state.stopwatch.reached(self.limit) {
+            let child_state = VacantState::take(&mut state.child_state);
+            self.child.force_exit(world, entity, child_state);
+            return Some(self.result);
+        }
+        self.child
+            .resume_in_place(world, entity, &mut state.child_state)
+    }
+    fn force_exit(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) {
+        let state = Self::downcast(state).expect("Invalid state.");
+        self.child.force_exit(world, entity, state.child_state);
+    }
+}
+
+/// State for [`Timeout`]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(NodeState, Debug)]
+struct TimeoutState {
+    stopwatch: Stopwatch,
+    child_state: Box<dyn NodeState>,
+}
+
+/// Last completions of the [`Cooldown`] nodes, for each node instance.
+/// Added to the entity by the node. Clear it to reset the cooldowns.
+/// Completions are dropped once their period has passed.
+#[derive(Component, Debug, Default)]
+pub struct CooldownMemory {
+    completed: HashMap<u64, Completion>,
+}
+impl CooldownMemory {
+    pub fn clear(&mut self) {
+        self.completed.clear();
+    }
+}
+
+/// Random id of [`Cooldown`], distinct between the instances including the ones from other runs.
+fn new_cooldown_id() -> u64 {
+    RandomState::new().hash_one(0u8)
+}
+
+/// Completion of [`Cooldown`], with how to tell that it has expired.
+#[derive(Debug, Clone, Copy)]
+struct Completion {
+    at: Timestamp,
+    period: Period,
+    time: TimeSource,
+}
+impl Completion {
+    fn expired(&self, world: &World) -> bool {
+        Timestamp::now(self.time, world).passed(&self.at, self.period)
+    }
+}
+
+/// Node that returns Failure without running the child,
+///  until the period has passed since the child completed last time.
+/// The completion is remembered in [`CooldownMemory`] of the entity under the id of the node,
+///  since the state of the node is dropped on completion.
+/// The id is assigned on construction and serialized, so that reloaded trees keep the cooldown.
+/// Trees written by hand get a new id on each load unless they set `id`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug)]
+#[with_state(CooldownState)]
+pub struct Cooldown {
+    #[cfg_attr(feature = "serde", serde(default = "new_cooldown_id"))]
+    id: u64,
+    child: Box<dyn Node>,
+    period: Period,
+    time: TimeSource,
+}
+impl Cooldown {
+    pub fn new(child: impl Node, period: Period) -> Self {
+        Self {
+            id: new_cooldown_id(),
+            child: Box::new(child),
+            period,
+            time: TimeSource::default(),
+        }
+    }
+    /// Reads the given clock rather than default [`TimeSource::Virtual`].
+    pub fn with_time(mut self, time: TimeSource) -> Self {
+        self.time = time;
+        self
+    }
+    fn wrap(&self, world: &mut World, entity: Entity, child_status: NodeStatus) -> NodeStatus {
+        match child_status {
+            NodeStatus::Pending(child_state) => {
+                NodeStatus::Pending(Box::new(CooldownState { child_state }))
+            }
+            NodeStatus::Complete(_) => {
+                self.remember(world, entity);
+                child_status
+            }
+            NodeStatus::Beginning => panic!("Invalid child status."),
+        }
+    }
+    /// Records the completion, dropping the expired ones.
+    fn remember(&self, world: &mut World, entity: Entity) {
+        let completion = Completion {
+            at: Timestamp::now(self.time, world),
+            period: self.period,
+            time: self.time,
+        };
+        let expired: Vec<u64> = world
+            .get::<CooldownMemory>(entity)
+            .map(|memory| {
+                memory
+                    .completed
+                    .iter()
+                    .filter(|(_, completion)| completion.expired(world))
+                    .map(|(&id, _)| id)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let mut entity = world.entity_mut(entity);
+        let mut memory = entity.entry::<CooldownMemory>().or_default().into_mut();
+        for id in expired {
+            memory.completed.remove(&id);
+        }
+        memory.completed.insert(self.id, completion);
+    }
+}
+#[cfg_attr(feature = "serde", typetag::serde)]
+impl Node for Cooldown {
+    fn begin(&self, world: &mut World, entity: Entity) -> NodeStatus {
+        let now = Timestamp::now(self.time, world);
+        let cooling = world
+            .get::<CooldownMemory>(entity)
+            .and_then(|memory| memory.completed.get(&self.id))
+            .is_some_and(|completion| !now.passed(&completion.at, self.period));
+        if cooling {
+            return NodeStatus::Complete(NodeResult::Failure);
+        }
+        let child_status = self.child.begin(world, entity);
+        self.wrap(world, entity, child_status)
+    }
+    fn resume(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        mut state: Box<dyn NodeState>,
+    ) -> NodeStatus {
+        let result = self.resume_in_place(world, entity, &mut state);
+        NodeStatus::from_resumed(result, state)
+    }
+    fn resume_in_place(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        state: &mut Box<dyn NodeState>,
+    ) -> Option<NodeResult> {
+        let state = Self::downcast_mut(state).expect("Invalid state.");
+        let result = self
+            .child
+            .resume_in_place(world, entity, &mut state.child_state)?;
+        self.remember(world, entity);
+        Some(result)
+    }
+    fn force_exit(&self, world: &mut World, entity: Entity, state: Box<dyn NodeState>) {
+        let state = Self::downcast(state).expect("Invalid state.");
+        self.child.force_exit(world, entity, state.child_state);
+    }
+}
+
+/// State for [`Cooldown`]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(NodeState, Debug)]
+struct CooldownState {
+    child_state: Box<dyn NodeState>,
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::time::TimeUpdateStrategy;
+
+    use super::*;
+    use crate::tester_util::prelude::*;
+
+    fn spawn_wait(app: &mut App, wait: Wait) -> Entity {
+        let sequence = SequentialAnd::new(vec![
+            Box::new(wait),
+            Box::new(TesterTask0::new(1, NodeResult::Success)),
+        ]);
+        let tree = BehaviorTree::from_node(
+            sequence,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        app.world_mut().spawn(tree).id()
+    }
+
+    fn time_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((TesterPlugin, BehaviorTreePlugin::default()))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )));
+        app
+    }
+
+    #[test]
+    fn test_wait() {
+        let mut app = time_app();
+        spawn_wait(&mut app, Wait::new(Duration::from_millis(250)));
+        for _ in 0..6 {
+            app.update();
+        }
+        let found = log_frames(&app);
+        assert!(
+            found == vec![4],
+            "Wait should complete after the duration. found: {:?}",
+            found
+        );
+    }
+
+    #[test]
+    fn test_wait_paused() {
+        let mut app = time_app();
+        spawn_wait(&mut app, Wait::new(Duration::from_millis(250)));
+        app.update();
+        app.world_mut().resource_mut::<Time<Virtual>>().pause();
+        for _ in 0..5 {
+            app.update();
+        }
+        assert!(
+            log_frames(&app).is_empty(),
+            "Wait should not proceed while paused."
+        );
+        app.world_mut().resource_mut::<Time<Virtual>>().unpause();
+        for _ in 0..5 {
+            app.update();
+        }
+        let found = log_frames(&app);
+        assert!(
+            found == vec![9],
+            "Wait should resume after unpause. found: {:?}",
+            found
+        );
+    }
+
+    #[test]
+    fn test_wait_real_time_ignores_pause() {
+        let mut app = time_app();
+        spawn_wait(
+            &mut app,
+            Wait::new(Duration::from_millis(250)).with_time(TimeSource::Real),
+        );
+        app.update();
+        app.world_mut().resource_mut::<Time<Virtual>>().pause();
+        for _ in 0..5 {
+            app.update();
+        }
+        let found = log_frames(&app);
+        assert!(
+            found == vec![4],
+            "Wait on real time should proceed while paused. found: {:?}",
+            found
+        );
+    }
+
+    #[test]
+    fn test_timeout_frames() {
+        let mut app = time_app();
+        let timeout = Timeout::new(TesterTask0::new(5, NodeResult::Success), Period::Frames(2));
+        let tree = BehaviorTree::from_node(
+            timeout,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        app.update(); // begin
+        app.update(); // 1
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_some(),
+            "Child should be running within the limit."
+        );
+        app.update(); // 2, timeout
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_none(),
+            "Task of the child should exit on timeout."
+        );
+        let status = app.world().get::<TreeStatus>(entity).unwrap();
+        assert!(
+            status.result() == Some(NodeResult::Failure),
+            "Timeout should complete with Failure. found: {:?}",
+            status
+        );
+    }
+
+    #[test]
+    fn test_timeout_time_with_result() {
+        let mut app = time_app();
+        let timeout = Timeout::new(
+            TesterTask0::new(10, NodeResult::Failure),
+            Period::Time(Duration::from_millis(250)),
+        )
+        .with_result(NodeResult::Success);
+        let tree = BehaviorTree::from_node(
+            timeout,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        for _ in 0..6 {
+            app.update();
+        }
+        assert!(
+            app.world().get::<TesterComponent<0>>(entity).is_none(),
+            "Task of the child should exit on timeout."
+        );
+        let status = app.world().get::<TreeStatus>(entity).unwrap();
+        assert!(
+            status.result() == Some(NodeResult::Success),
+            "Timeout should complete with the configured result. found: {:?}",
+            status
+        );
+    }
+
+    #[test]
+    fn test_timeout_child_completes() {
+        let mut app = time_app();
+        let timeout = Timeout::new(TesterTask0::new(1, NodeResult::Success), Period::Frames(5));
+        let tree = BehaviorTree::from_node(
+            timeout,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        for _ in 0..3 {
+            app.update();
+        }
+        let status = app.world().get::<TreeStatus>(entity).unwrap();
+        assert!(
+            status.result() == Some(NodeResult::Success),
+            "Result of the child should pass through within the limit. found: {:?}",
+            status
+        );
+    }
+
+    fn spawn_cooldown(app: &mut App, cooldown: Cooldown) -> Entity {
+        let tree = BehaviorTree::from_node(
+            cooldown,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        app.world_mut().spawn((tree, RestartPolicy::Always)).id()
+    }
+
+    #[test]
+    fn test_cooldown_time() {
+        let mut app = time_app();
+        spawn_cooldown(
+            &mut app,
+            Cooldown::new(
+                TesterTask0::new(1, NodeResult::Success),
+                Period::Time(Duration::from_millis(350)),
+            ),
+        );
+        for _ in 0..10 {
+            app.update();
+        }
+        let found = log_frames(&app);
+        assert!(
+            found == vec![1, 6],
+            "Child should run again after the cooldown. found: {:?}",
+            found
+        );
+    }
+
+    #[test]
+    fn test_cooldown_frames_per_entity() {
+        let mut app = time_app();
+        let tree = BehaviorTree::from_node(
+            Cooldown::new(TesterTask0::new(1, NodeResult::Success), Period::Frames(3)),
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let first = app
+            .world_mut()
+            .spawn((tree.clone(), RestartPolicy::Always))
+            .id();
+        app.update();
+        app.update(); // complete on first
+        let second = app.world_mut().spawn((tree, RestartPolicy::Always)).id();
+        app.update();
+        assert!(
+            app.world().get::<TesterComponent<0>>(second).is_some(),
+            "Cooldown should be per entity."
+        );
+        assert!(
+            app.world().get::<TesterComponent<0>>(first).is_none(),
+            "Child should not run while cooling down."
+        );
+        let status = app.world().get::<TreeStatus>(first).unwrap();
+        assert!(
+            status.result() == Some(NodeResult::Failure),
+            "Cooldown should return Failure while cooling down. found: {:?}",
+            status
+        );
+    }
+
+    #[test]
+    fn test_cooldown_prunes_expired() {
+        let mut app = time_app();
+        let long = Cooldown::new(
+            TesterTask2::new(0, NodeResult::Success),
+            Period::Frames(100),
+        );
+        let long_id = long.id;
+        let sequence = SequentialAnd::new(vec![
+            Box::new(Cooldown::new(
+                TesterTask0::new(0, NodeResult::Success),
+                Period::Frames(1),
+            )),
+            Box::new(TesterTask1::new(2, NodeResult::Success)),
+            Box::new(long),
+        ]);
+        let tree = BehaviorTree::from_node(
+            sequence,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        for _ in 0..5 {
+            app.update();
+        }
+        let found: Vec<_> = app
+            .world()
+            .get::<CooldownMemory>(entity)
+            .unwrap()
+            .completed
+            .keys()
+            .cloned()
+            .collect();
+        assert!(
+            found == vec![long_id],
+            "Expired completions should be dropped. found: {:?}",
+            found
+        );
+    }
+
+    #[test]
+    fn test_cooldown_per_node() {
+        let mut app = time_app();
+        let sequence = SequentialAnd::new(vec![
+            Box::new(Cooldown::new(
+                TesterTask0::new(0, NodeResult::Success),
+                Period::Frames(100),
+            )),
+            Box::new(Cooldown::new(
+                TesterTask1::new(0, NodeResult::Success),
+                Period::Frames(100),
+            )),
+        ]);
+        let tree = BehaviorTree::from_node(
+            sequence,
+            &mut app.world_mut().resource_mut::<Assets<BehaviorTreeRoot>>(),
+        );
+        let entity = app.world_mut().spawn(tree).id();
+        for _ in 0..3 {
+            app.update();
+        }
+        let status = app.world().get::<TreeStatus>(entity).unwrap();
+        let found = app
+            .world()
+            .get::<CooldownMemory>(entity)
+            .unwrap()
+            .completed
+            .len();
+        assert!(
+            status.result() == Some(NodeResult::Success) && found == 2,
+            "Cooldowns in one tree should not share the completion. found: {:?}, {}",
+            status,
+            found
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_cooldown_kept_on_reload() {
+        let root = BehaviorTreeRoot {
+            node: std::sync::Arc::new(Cooldown::new(
+                TesterTask0::new(1, NodeResult::Success),
+                Period::Frames(100),
+            )),
+        };
+        let ron = ron::to_string(&root).expect("Failed to serialize.");
+        let mut app = time_app();
+        let load = |app: &mut App| {
+            let root: BehaviorTreeRoot = ron::from_str(&ron).expect("Failed to deserialize.");
+            BehaviorTree::new(
+                app.world_mut()
+                    .resource_mut::<Assets<BehaviorTreeRoot>>()
+                    .add(root),
+            )
+        };
+        let tree = load(&mut app);
+        let entity = app.world_mut().spawn((tree, RestartPolicy::Always)).id();
+        for _ in 0..3 {
+            app.update();
+        }
+        let tree = load(&mut app);
+        app.world_mut().entity_mut(entity).insert(tree);
+        for _ in 0..3 {
+            app.update();
+        }
+        let found = log_frames(&app);
+        assert!(
+            found == vec![1],
+            "Reloaded tree should keep the cooldown. found: {:?}",
+            found
+        );
+    }
+
+    #[cfg(feature = "random")]
+    #[test]
+    fn test_wait_random() {
+        let mut app = time_app();
+        spawn_wait(
+            &mut app,
+            Wait::random(Duration::from_millis(150), Duration::from_millis(350)),
+        );
+        for _ in 0..7 {
+            app.update();
+        }
+        let found = log_frames(&app);
+        assert!(
+            found.len() == 1 && (3..=5).contains(&found[0]),
+            "Wait should complete within the range. found: {:?}",
+            found
+        );
+    }
+
+    #[cfg(feature = "random")]
+    #[test]
+    fn test_wait_random_reversed() {
+        let mut app = time_app();
+        spawn_wait(
+            &mut app,
+            Wait::random(Duration::from_millis(350), Duration::from_millis(150)),
+        );
+        for _ in 0..7 {
+            app.update();
+        }
+        let found = log_frames(&app);
+        assert!(
+            found.len() == 1 && (3..=5).contains(&found[0]),
+            "Wait should complete within the reversed range. found: {:?}",
+            found
+        );
+    }
+}